@@ -0,0 +1,266 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command as Process, Stdio},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use super::{Budget, AI};
+use crate::{
+    game::{Board, GameOutcome, Move, Species},
+    protocol::{Command, Reply},
+};
+
+/// How long an engine may take to answer the handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long past its movetime an engine may take to answer `go`.
+const MOVETIME_GRACE: Duration = Duration::from_secs(1);
+/// How long an engine may take to answer `go` without a movetime.
+const UNTIMED_MOVE_TIMEOUT: Duration = Duration::from_secs(60);
+/// How often a wait for the engine checks whether it was stopped.
+const STOP_POLL: Duration = Duration::from_millis(10);
+
+/// Plays moves chosen by an external executable speaking [`crate::protocol`].
+/// The process is started on the first move request and restarted if it dies,
+/// times out or is stopped while thinking.
+#[derive(Debug)]
+pub struct ExternalAI {
+    program: String,
+    args: Vec<String>,
+    process: Option<EngineProcess>,
+    handshake_timeout: Duration,
+    move_timeout: Duration,
+    /// Why the last move request failed, until taken.
+    error: Option<String>,
+}
+
+#[derive(Debug)]
+struct EngineProcess {
+    child: Child,
+    input: ChildStdin,
+    /// Lines of the engine's output, read on their own thread so waits can time out.
+    output: Receiver<String>,
+}
+
+impl EngineProcess {
+    fn spawn(program: &str, args: &[String], timeout: Duration) -> io::Result<Self> {
+        let mut child = Process::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let input = child.stdin.take().expect("stdin is piped");
+        let stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+        let (lines, output) = mpsc::channel();
+        thread::spawn(move || {
+            for line in stdout.lines().map_while(Result::ok) {
+                if lines.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        let mut process = Self {
+            child,
+            input,
+            output,
+        };
+        process.send(&Command::Wsp)?;
+        let deadline = Instant::now() + timeout;
+        process.wait_for(|reply| matches!(reply, Reply::WspOk), deadline, &AtomicBool::new(false))?;
+        process.send(&Command::NewGame)?;
+        Ok(process)
+    }
+
+    fn send(&mut self, command: &Command) -> io::Result<()> {
+        writeln!(self.input, "{command}")?;
+        self.input.flush()
+    }
+
+    /// Waits for the expected reply until `deadline`, giving up with
+    /// [`io::ErrorKind::Interrupted`] once `stop` is set.
+    fn wait_for(
+        &mut self,
+        expected: impl Fn(&Reply) -> bool,
+        deadline: Instant,
+        stop: &AtomicBool,
+    ) -> io::Result<Reply> {
+        loop {
+            if stop.load(Ordering::Relaxed) {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "stopped"));
+            }
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                return Err(io::Error::new(io::ErrorKind::TimedOut, "engine didn't answer in time"));
+            }
+            let line = match self.output.recv_timeout(left.min(STOP_POLL)) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "engine closed its output",
+                    ))
+                }
+            };
+            // unknown lines and infos are tolerated, like UCI guis do
+            if let Ok(reply) = line.parse() {
+                if expected(&reply) {
+                    return Ok(reply);
+                }
+            }
+        }
+    }
+
+    fn best_move(
+        &mut self,
+        board: &Board,
+        budget: &Budget,
+        untimed: Duration,
+        stop: &AtomicBool,
+    ) -> io::Result<Option<Move>> {
+        self.send(&Command::Position(board.clone()))?;
+        self.send(&Command::Go(budget.clone()))?;
+        let timeout = budget.movetime.map_or(untimed, |movetime| movetime + MOVETIME_GRACE);
+        match self.wait_for(|reply| matches!(reply, Reply::BestMove(_)), Instant::now() + timeout, stop)? {
+            Reply::BestMove(mv) => Ok(mv),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Asks the engine to quit and kills it if it hasn't yet, so a stuck engine
+/// can't hold up whoever dropped it.
+impl Drop for EngineProcess {
+    fn drop(&mut self) {
+        let _ = self.send(&Command::Quit);
+        if !matches!(self.child.try_wait(), Ok(Some(_))) {
+            let _ = self.child.kill();
+        }
+        // reaps the exited process, which is quick after a kill
+        let _ = self.child.wait();
+    }
+}
+
+impl ExternalAI {
+    /// `command_line` is the executable followed by its arguments, separated by whitespace.
+    pub fn new(command_line: &str) -> Self {
+        let mut parts = command_line.split_whitespace().map(str::to_owned);
+        Self {
+            program: parts.next().unwrap_or_default(),
+            args: parts.collect(),
+            process: None,
+            handshake_timeout: HANDSHAKE_TIMEOUT,
+            move_timeout: UNTIMED_MOVE_TIMEOUT,
+            error: None,
+        }
+    }
+
+    fn process(&mut self) -> io::Result<&mut EngineProcess> {
+        match self.process {
+            Some(ref mut process) => Ok(process),
+            None => Ok(self.process.insert(EngineProcess::spawn(
+                &self.program,
+                &self.args,
+                self.handshake_timeout,
+            )?)),
+        }
+    }
+}
+
+impl AI for ExternalAI {
+    fn next_move(&mut self, board: &Board) -> Option<Move> {
+        self.next_move_within(board, &Budget::default(), &AtomicBool::new(false))
+    }
+
+    /// The budget is passed on to the engine, which gets a second more than
+    /// its movetime to answer. An engine that doesn't, or is stopped while
+    /// thinking, is restarted for the next move.
+    fn next_move_within(&mut self, board: &Board, budget: &Budget, stop: &AtomicBool) -> Option<Move> {
+        let untimed = self.move_timeout;
+        match self.process().and_then(|process| process.best_move(board, budget, untimed, stop)) {
+            Ok(mv) => mv,
+            Err(e) => {
+                if e.kind() != io::ErrorKind::Interrupted {
+                    self.error = Some(format!("External AI '{}' failed: {e}", self.program));
+                }
+                self.process = None;
+                None
+            }
+        }
+    }
+
+    fn take_error(&mut self) -> Option<String> {
+        self.error.take()
+    }
+
+    fn feedback(&mut self, _outcome: &GameOutcome, _played_as: &Species) {
+        if let Some(process) = &mut self.process {
+            if process.send(&Command::NewGame).is_err() {
+                self.process = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPTED_ENGINE: &str = "while read cmd rest; do case $cmd in \
+        wsp) echo 'id name scripted'; echo wspok;; \
+        go) echo 'info string thinking'; echo 'bestmove d8c7';; \
+        quit) exit;; \
+        esac; done";
+
+    /// Engine run by `sh` from `script`.
+    fn scripted(script: &str) -> ExternalAI {
+        ExternalAI {
+            args: vec!["-c".to_owned(), script.to_owned()],
+            ..ExternalAI::new("sh")
+        }
+    }
+
+    #[test]
+    fn next_move_should_parse_best_move_from_engine() {
+        let mut ai = scripted(SCRIPTED_ENGINE);
+
+        let mv = ai.next_move(&Board::default());
+
+        assert_eq!(mv, Some("d8c7".parse().unwrap()));
+        assert_eq!(ai.take_error(), None);
+    }
+
+    #[test]
+    fn next_move_should_report_an_engine_that_cannot_start() {
+        let mut ai = ExternalAI::new("./definitely-not-an-engine");
+
+        assert_eq!(ai.next_move(&Board::default()), None);
+        assert!(ai.take_error().is_some_and(|e| e.contains("definitely-not-an-engine")));
+    }
+
+    #[test]
+    fn next_move_should_give_up_on_an_engine_that_never_answers() {
+        let mut ai = ExternalAI {
+            handshake_timeout: Duration::from_millis(50),
+            ..scripted("cat > /dev/null")
+        };
+
+        assert_eq!(ai.next_move(&Board::default()), None);
+        assert!(ai.take_error().is_some_and(|e| e.contains("in time")));
+        assert!(ai.process.is_none(), "the engine should be dropped for a fresh start");
+    }
+
+    #[test]
+    fn next_move_should_return_once_stopped() {
+        let mut ai = scripted("echo wspok; cat > /dev/null");
+
+        let mv = ai.next_move_within(&Board::default(), &Budget::default(), &AtomicBool::new(true));
+
+        assert_eq!(mv, None);
+        assert_eq!(ai.take_error(), None, "being stopped isn't a failure");
+    }
+}
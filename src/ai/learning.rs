@@ -1,4 +1,4 @@
-use crate::game::{Board, engine, Species};
+use crate::game::{Board, engine, Rules, Species};
 
 use super::{AI, get_ai, AITypes};
 
@@ -9,6 +9,12 @@ pub struct LearningProgress {
     pub max: u32,
 }
 
+impl Default for LearningProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LearningProgress {
     pub fn new() -> Self {
         Self {
@@ -31,33 +37,34 @@ impl LearningProgress {
 }
 
 pub fn learning_session(ai: &mut dyn AI, species: Species) {
-    let mut opponent = get_ai(AITypes::Smart);
+    let rules = Rules::default();
+    let mut opponent = get_ai(AITypes::Smart, &rules);
     let mut board = Board::default();
     match species {
         Species::Wolf => loop {
             if let Some(first_move) = &ai.next_move(&board) {
-                if engine::handle_move(&mut board, first_move).is_some() {
-                    ai.feedback(true);
+                if let Some(outcome) = engine::handle_move(&mut board, first_move, &rules) {
+                    ai.feedback(&outcome, &species);
                     break;
                 }
             }
             if let Some(second_move) = &opponent.next_move(&board) {
-                if engine::handle_move(&mut board, second_move).is_some() {
-                    ai.feedback(false);
+                if let Some(outcome) = engine::handle_move(&mut board, second_move, &rules) {
+                    ai.feedback(&outcome, &species);
                     break;
                 }
             }
         },
         Species::Sheep => loop {
             if let Some(first_move) = &opponent.next_move(&board) {
-                if engine::handle_move(&mut board, first_move).is_some() {
-                    ai.feedback(false);
+                if let Some(outcome) = engine::handle_move(&mut board, first_move, &rules) {
+                    ai.feedback(&outcome, &species);
                     break;
                 }
             }
             if let Some(second_move) = &ai.next_move(&board) {
-                if engine::handle_move(&mut board, second_move).is_some() {
-                    ai.feedback(true);
+                if let Some(outcome) = engine::handle_move(&mut board, second_move, &rules) {
+                    ai.feedback(&outcome, &species);
                     break;
                 }
             }
@@ -1,3 +1,4 @@
+#[allow(dead_code)]
 pub struct LinearFunctionAI {
     stored_variables: [f32; 6],
 }
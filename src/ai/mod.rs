@@ -1,28 +1,179 @@
+mod external_ai;
 mod linear_function_ai;
 mod random_ai;
 mod remembrance_ai;
+mod search_ai;
 pub mod learning;
 mod smart_ai;
 
-use crate::game::{Board, Move};
+use crate::game::{Board, GameOutcome, Move, Rules, Species};
 
-use std::fmt::Debug;
+use std::{
+    fmt::{self, Debug},
+    str::FromStr,
+    sync::atomic::AtomicBool,
+    time::Duration,
+};
 
+pub use remembrance_ai::{state_is_lost_for_sheep, RemembranceAI};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum AITypes {
     Random,
     Remembrance,
     Smart,
+    /// Looks `depth` moves ahead, the deeper the stronger.
+    Search { depth: u8 },
+    /// Command line of an executable speaking [`crate::protocol`].
+    External(String),
+}
+
+pub const DEFAULT_SEARCH_DEPTH: u8 = 4;
+
+impl AITypes {
+    /// AI a side gets when switched to computer control without picking one.
+    pub fn default_for(species: &Species) -> AITypes {
+        match species {
+            Species::Wolf => AITypes::Smart,
+            Species::Sheep => AITypes::Random,
+        }
+    }
+
+    /// Smart only knows how to play the wolf and Remembrance the sheep.
+    pub fn can_play(&self, species: &Species) -> bool {
+        match self {
+            AITypes::Smart => *species == Species::Wolf,
+            AITypes::Remembrance => *species == Species::Sheep,
+            AITypes::Random | AITypes::Search { .. } | AITypes::External(_) => true,
+        }
+    }
+}
+
+impl fmt::Display for AITypes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AITypes::Random => write!(f, "random"),
+            AITypes::Remembrance => write!(f, "remembrance"),
+            AITypes::Smart => write!(f, "smart"),
+            AITypes::Search { depth } => write!(f, "search:{depth}"),
+            AITypes::External(command_line) => write!(f, "external:{command_line}"),
+        }
+    }
+}
+
+impl FromStr for AITypes {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "random" => Ok(AITypes::Random),
+            "remembrance" => Ok(AITypes::Remembrance),
+            "smart" => Ok(AITypes::Smart),
+            "search" => Ok(AITypes::Search {
+                depth: DEFAULT_SEARCH_DEPTH,
+            }),
+            _ if s.starts_with("search:") => s["search:".len()..]
+                .parse()
+                .map(|depth| AITypes::Search { depth })
+                .map_err(|_| format!("invalid search depth in '{s}'")),
+            _ if s.starts_with("external:") => {
+                Ok(AITypes::External(s["external:".len()..].to_owned()))
+            }
+            _ => Err(format!("unknown AI type '{s}'")),
+        }
+    }
+}
+
+/// How much an AI may spend on a single move, unlimited by default.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Budget {
+    /// Deepest a search may look, overriding the AI's own depth.
+    pub depth: Option<u8>,
+    pub movetime: Option<Duration>,
+    /// Positions a search may visit.
+    pub nodes: Option<u64>,
+}
+
+impl Budget {
+    pub fn movetime(movetime: Duration) -> Self {
+        Self {
+            movetime: Some(movetime),
+            ..Default::default()
+        }
+    }
+
+    pub fn nodes(nodes: u64) -> Self {
+        Self {
+            nodes: Some(nodes),
+            ..Default::default()
+        }
+    }
+}
+
+impl fmt::Display for Budget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut limits = Vec::new();
+        if let Some(depth) = self.depth {
+            limits.push(format!("depth {depth}"));
+        }
+        if let Some(movetime) = self.movetime {
+            limits.push(format!("{} ms", movetime.as_millis()));
+        }
+        if let Some(nodes) = self.nodes {
+            limits.push(format!("{nodes} nodes"));
+        }
+        match limits.is_empty() {
+            true => write!(f, "no limit"),
+            false => write!(f, "{}", limits.join(", ")),
+        }
+    }
 }
 
 pub trait AI: Debug {
     fn next_move(&mut self, board: &Board) -> Option<Move>;
-    fn feedback(&mut self, won: bool);
+    /// Why the last move request came back empty, if the AI failed rather
+    /// than gave up. Only AIs that can break, like external ones, report anything.
+    fn take_error(&mut self) -> Option<String> {
+        None
+    }
+    /// Like [`AI::next_move`], but keeping to `budget` and allowed to give up
+    /// early once `stop` is set, as the answer won't be used anymore.
+    /// AIs that answer right away can ignore both.
+    fn next_move_within(&mut self, board: &Board, budget: &Budget, stop: &AtomicBool) -> Option<Move> {
+        let _ = (budget, stop);
+        self.next_move(board)
+    }
+    fn feedback(&mut self, outcome: &GameOutcome, played_as: &Species);
+}
+
+/// An AI playing one side, remembering which kind it was created as.
+#[derive(Debug)]
+pub struct ComputerPlayer {
+    pub kind: AITypes,
+    pub ai: Box<dyn AI + Send>,
+}
+
+impl ComputerPlayer {
+    pub fn new(kind: AITypes, rules: &Rules) -> Self {
+        Self {
+            ai: get_ai(kind.clone(), rules),
+            kind,
+        }
+    }
 }
 
-pub fn get_ai(ai_type: AITypes) -> Box<dyn AI + Send> {
+/// AI of `ai_type` for games played under `rules`, which only the search AI looks ahead far enough to need.
+pub fn get_ai(ai_type: AITypes, rules: &Rules) -> Box<dyn AI + Send> {
     match ai_type {
         AITypes::Random => Box::new(random_ai::RandomAI::new()),
         AITypes::Remembrance => Box::new(remembrance_ai::RemembranceAI::new()),
         AITypes::Smart => Box::new(smart_ai::SmartAI {}),
+        AITypes::Search { depth } => Box::new(search_ai::SearchAI::new(depth, rules.clone())),
+        AITypes::External(command_line) => Box::new(external_ai::ExternalAI::new(&command_line)),
     }
 }
@@ -1,6 +1,6 @@
 use crate::game::{
     movement::{all_available_sheeps_moves, all_available_wolf_moves},
-    Board, Move, Species,
+    Board, GameOutcome, Move, Species,
 };
 
 use super::AI;
@@ -25,17 +25,17 @@ fn random_from_arr<T>(a: &[T]) -> Option<&T> {
 }
 
 impl AI for RandomAI {
-    fn feedback(&mut self, won: bool) {}
+    fn feedback(&mut self, _outcome: &GameOutcome, _played_as: &Species) {}
 
     fn next_move(&mut self, board: &Board) -> Option<Move> {
         match board.currently_moving {
             Species::Wolf => {
                 let available_moves = all_available_wolf_moves(&board.wolf, &board.sheeps);
-                random_from_arr(&available_moves).map(|random_move| random_move.clone())
+                random_from_arr(&available_moves).cloned()
             }
             Species::Sheep => {
                 let available_sheep_moves = all_available_sheeps_moves(board);
-                random_from_arr(&available_sheep_moves).map(|random_move| random_move.clone())
+                random_from_arr(&available_sheep_moves).cloned()
             }
         }
     }
@@ -1,16 +1,17 @@
 use super::AI;
 use crate::game::{
     movement::{all_available_sheeps_moves, all_available_wolf_moves},
-    Board, Coord, Move, Species,
+    Board, Coord, GameOutcome, Move, Species,
 };
 use std::collections::{HashMap, HashSet};
 
-type Sheeps = [Coord; 4];
+type Sheeps = Vec<Coord>;
 
-#[derive(Debug)]
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RemembranceAI {
     losing_states: HashMap<Coord, HashSet<Sheeps>>,
-    previous_move: (Coord, [Coord; 4]),
+    previous_move: Option<(Coord, Sheeps)>,
 }
 
 impl RemembranceAI {
@@ -23,51 +24,31 @@ impl RemembranceAI {
     #[cfg(test)]
     pub fn setup(
         losing_states: HashMap<Coord, HashSet<Sheeps>>,
-        previous_move: (Coord, [Coord; 4]),
+        previous_move: (Coord, Sheeps),
     ) -> Self {
         Self {
             losing_states,
-            previous_move,
+            previous_move: Some(previous_move),
         }
     }
 }
 
-impl Default for RemembranceAI {
-    fn default() -> Self {
-        Self {
-            losing_states: Default::default(),
-            previous_move: (
-                Coord::new(0, 0),
-                [
-                    Coord::new(0, 0),
-                    Coord::new(0, 0),
-                    Coord::new(0, 0),
-                    Coord::new(0, 0),
-                ],
-            ),
-        }
-    }
-}
-
-pub fn state_after_sheep_move(s_move: &Move, sheeps: &[Coord]) -> [Coord; 4] {
-    let mut to_ret = [
-        sheeps[0].clone(),
-        sheeps[1].clone(),
-        sheeps[2].clone(),
-        sheeps[3].clone(),
-    ];
+pub fn state_after_sheep_move(s_move: &Move, sheeps: &[Coord]) -> Sheeps {
+    let mut to_ret = sheeps.to_vec();
     let to_swap = to_ret.iter_mut().find(|s| **s == s_move.from).unwrap();
     *to_swap = s_move.to.clone();
     to_ret
 }
 
 pub fn state_is_lost_for_sheep(sheeps: &[Coord], wolf: &Coord) -> bool {
-    let last_sheep_y = sheeps.iter().map(|s| s.y).max().unwrap();
-    if wolf.y >= last_sheep_y {
+    let Some(last_sheep_y) = sheeps.iter().map(|s| s.y()).max() else {
+        return true;
+    };
+    if wolf.y() >= last_sheep_y {
         return true;
     }
     let possible_wolf_moves = all_available_wolf_moves(wolf, sheeps);
-    possible_wolf_moves.iter().any(|mv| mv.to.y >= last_sheep_y)
+    possible_wolf_moves.iter().any(|mv| mv.to.y() >= last_sheep_y)
 }
 
 fn move_based_on_data(ai: &mut RemembranceAI, board: &Board, available_moves: &[Move]) -> Move {
@@ -79,26 +60,28 @@ fn move_based_on_data(ai: &mut RemembranceAI, board: &Board, available_moves: &[
             .map(|s_move| (s_move, state_after_sheep_move(s_move, &board.sheeps)))
             .find(|(_, state)| !losing_states.contains(state));
         if let Some((mv, state)) = ok_possible_state {
-            ai.previous_move = (board.wolf.clone(), state);
+            ai.previous_move = Some((board.wolf.clone(), state));
             mv.clone()
         } else {
             mark_previous_move_as_fail(ai);
             available_moves[0].clone()
         }
     } else {
-        ai.previous_move = (board.wolf.clone(), state_after_sheep_move(&available_moves[0], &board.sheeps));
+        ai.previous_move = Some((board.wolf.clone(), state_after_sheep_move(&available_moves[0], &board.sheeps)));
         available_moves[0].clone()
     }
 }
 
 pub fn mark_previous_move_as_fail(ai: &mut RemembranceAI) {
-    let previous_states = ai.losing_states.entry(ai.previous_move.0.clone()).or_default();
-    previous_states.insert(ai.previous_move.1.clone());
+    if let Some((wolf, sheeps)) = &ai.previous_move {
+        let previous_states = ai.losing_states.entry(wolf.clone()).or_default();
+        previous_states.insert(sheeps.clone());
+    }
 }
 
 impl AI for RemembranceAI {
-    fn feedback(&mut self, won: bool) {
-        if !won {
+    fn feedback(&mut self, outcome: &GameOutcome, played_as: &Species) {
+        if !outcome.is_win_for(played_as) {
             mark_previous_move_as_fail(self);
         }
     }
@@ -120,12 +103,13 @@ impl AI for RemembranceAI {
 mod tests {
     use super::*;
 
-    fn sheeps(a1: u8, b1: u8, a2: u8, b2: u8, a3: u8, b3: u8, a4: u8, b4: u8) -> [Coord; 4] {
-        [xy(a1, b1), xy(a2, b2), xy(a3, b3), xy(a4, b4)]
+    #[allow(clippy::too_many_arguments)]
+    fn sheeps(a1: u8, b1: u8, a2: u8, b2: u8, a3: u8, b3: u8, a4: u8, b4: u8) -> Sheeps {
+        vec![xy(a1, b1), xy(a2, b2), xy(a3, b3), xy(a4, b4)]
     }
 
     fn xy(a: u8, b: u8) -> Coord {
-        Coord::new(a, b)
+        Coord::new(a, b).unwrap()
     }
 
     fn mv_c(a: &Coord, b: &Coord) -> Move {
@@ -134,22 +118,22 @@ mod tests {
 
     #[test]
     fn state_after_sheep_move_should_change_single_sheep() {
-        let sheeps = sheeps(0, 0, 2, 2, 4, 6, 7, 7);
+        let sheeps = sheeps(1, 0, 3, 2, 5, 6, 6, 7);
         for sheep in &sheeps {
-            let sheeps = state_after_sheep_move(&mv_c(sheep, &xy(1, 3)), &sheeps);
-            assert!(sheeps.contains(&xy(1, 3)) && !sheeps.contains(sheep));
+            let sheeps = state_after_sheep_move(&mv_c(sheep, &xy(1, 2)), &sheeps);
+            assert!(sheeps.contains(&xy(1, 2)) && !sheeps.contains(sheep));
         }
     }
 
     #[test]
     fn state_is_lost_for_sheep_should_return_true_if_wolf_is_move_from_win() {
-        let sheepss = sheeps(3, 3, 4, 4, 5, 5, 6, 6);
+        let sheepss = sheeps(4, 3, 5, 4, 6, 5, 7, 6);
 
-        assert!(state_is_lost_for_sheep(&sheepss, &xy(3, 5)));
-        assert!(!state_is_lost_for_sheep(&sheepss, &xy(2, 4)));
+        assert!(state_is_lost_for_sheep(&sheepss, &xy(4, 5)));
+        assert!(!state_is_lost_for_sheep(&sheepss, &xy(3, 4)));
 
-        let sheeps = sheeps(0, 2, 2, 2, 4, 2, 6, 2);
-        for i in (1..8).step_by(2) {
+        let sheeps = sheeps(1, 2, 3, 2, 5, 2, 7, 2);
+        for i in (0..8).step_by(2) {
             assert!(!state_is_lost_for_sheep(&sheeps, &xy(i, 1)));
         }
     }
@@ -159,25 +143,42 @@ mod tests {
         let mut ai = RemembranceAI::setup(
             [
                 (
-                    xy(3, 3),
+                    xy(3, 4),
                     HashSet::from_iter(
-                        std::iter::once(sheeps(0, 0, 1, 1, 2, 2, 4, 4))
+                        std::iter::once(sheeps(1, 0, 2, 1, 3, 2, 5, 4))
                     ),
                 ),
             ]
             .into_iter()
             .collect(),
             (
-                Coord::new(3, 3),
-                sheeps(0, 0, 1, 1, 3, 3, 2, 2),
+                xy(3, 4),
+                sheeps(1, 0, 2, 1, 4, 3, 3, 2),
             ),
         );
 
         mark_previous_move_as_fail(&mut ai);
 
-        let found_move_in_data = ai.losing_states[&xy(3, 3)]
+        let found_move_in_data = ai.losing_states[&xy(3, 4)]
             .iter()
-            .find(|x| x[2] == xy(3, 3));
+            .find(|x| x[2] == xy(4, 3));
         assert!(found_move_in_data.is_some());
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn learned_states_should_survive_json_round_trip() {
+        let ai = RemembranceAI::setup(
+            [(xy(3, 4), HashSet::from_iter(std::iter::once(sheeps(1, 0, 2, 1, 3, 2, 5, 4))))]
+                .into_iter()
+                .collect(),
+            (xy(3, 4), sheeps(1, 0, 2, 1, 4, 3, 3, 2)),
+        );
+
+        let json = serde_json::to_string(&ai).unwrap();
+        let parsed: RemembranceAI = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.losing_states, ai.losing_states);
+        assert_eq!(parsed.previous_move, ai.previous_move);
+    }
 }
@@ -0,0 +1,265 @@
+use std::{
+    cell::Cell,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Instant,
+};
+
+use super::{Budget, AI};
+use crate::game::{
+    engine,
+    movement::{all_available_moves, all_available_wolf_moves},
+    Board, GameOutcome, Move, Rules, Species,
+};
+
+const WIN_SCORE: i32 = 10_000;
+
+/// Alpha-beta search playing either side, looking `depth` moves ahead.
+#[derive(Debug)]
+pub struct SearchAI {
+    depth: u8,
+    rules: Rules,
+}
+
+impl SearchAI {
+    pub fn new(depth: u8, rules: Rules) -> Self {
+        Self {
+            depth: depth.max(1),
+            rules,
+        }
+    }
+}
+
+/// Scores from the wolf's point of view: it wants room to move and a sheep
+/// line with gaps, sheep want to keep their line flat.
+fn evaluate(board: &Board) -> i32 {
+    let deepest_sheep = board.sheeps.iter().map(|s| s.y()).max().unwrap_or_default() as i32;
+    let highest_sheep = board.sheeps.iter().map(|s| s.y()).min().unwrap_or_default() as i32;
+    let wolf_moves = all_available_wolf_moves(&board.wolf, &board.sheeps).len() as i32;
+    wolf_moves * 10 + (deepest_sheep - highest_sheep) * 5 + board.wolf.y() as i32 * 2
+}
+
+/// Quicker wins and slower losses score better.
+fn outcome_score(outcome: &GameOutcome, ply: i32) -> i32 {
+    match &outcome.winner {
+        Some(Species::Wolf) => WIN_SCORE - ply,
+        Some(Species::Sheep) => ply - WIN_SCORE,
+        None => 0,
+    }
+}
+
+fn perspective(species: &Species) -> i32 {
+    match species {
+        Species::Wolf => 1,
+        Species::Sheep => -1,
+    }
+}
+
+/// One run of the search, abandoned once `stop` is set or the budget is spent.
+struct Search<'a> {
+    rules: &'a Rules,
+    stop: &'a AtomicBool,
+    deadline: Option<Instant>,
+    max_nodes: Option<u64>,
+    nodes: Cell<u64>,
+}
+
+impl<'a> Search<'a> {
+    fn new(rules: &'a Rules, budget: &Budget, stop: &'a AtomicBool) -> Self {
+        Self {
+            rules,
+            stop,
+            deadline: budget.movetime.map(|movetime| Instant::now() + movetime),
+            max_nodes: budget.nodes,
+            nodes: Cell::new(0),
+        }
+    }
+
+    fn exhausted(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+            || self.max_nodes.is_some_and(|max| self.nodes.get() >= max)
+            || self.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Negamax score of `board` for the side to move.
+    fn search(&self, board: &Board, depth: u8, ply: i32, mut alpha: i32, beta: i32) -> i32 {
+        if self.exhausted() {
+            // scores of an unfinished search are thrown away, so any will do
+            return 0;
+        }
+        self.nodes.set(self.nodes.get() + 1);
+        let sign = perspective(&board.currently_moving);
+        let moves = all_available_moves(board);
+        if depth == 0 || moves.is_empty() {
+            return sign * evaluate(board);
+        }
+        let mut best = -WIN_SCORE - 1;
+        for mv in moves {
+            let score = self.score_move(board, &mv, depth, ply, alpha, beta);
+            best = best.max(score);
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+        best
+    }
+
+    fn score_move(&self, board: &Board, mv: &Move, depth: u8, ply: i32, alpha: i32, beta: i32) -> i32 {
+        let mut next = board.clone();
+        match engine::handle_move(&mut next, mv, self.rules) {
+            Some(outcome) => perspective(&board.currently_moving) * outcome_score(&outcome, ply + 1),
+            // the opponent had to pass, so it's our turn again
+            None if next.currently_moving == board.currently_moving => {
+                self.search(&next, depth - 1, ply + 1, alpha, beta)
+            }
+            None => -self.search(&next, depth - 1, ply + 1, -beta, -alpha),
+        }
+    }
+
+    /// Index of the best of `moves` searched `depth` deep, judging only the
+    /// moves whose search finished within budget.
+    fn best_root_move(&self, board: &Board, moves: &[Move], depth: u8) -> Option<usize> {
+        let mut best: Option<(i32, usize)> = None;
+        for (index, mv) in moves.iter().enumerate() {
+            let alpha = best.map_or(-WIN_SCORE - 1, |(score, _)| score);
+            let score = self.score_move(board, mv, depth, 0, alpha, WIN_SCORE + 1);
+            if self.exhausted() {
+                break;
+            }
+            if best.is_none_or(|(best_score, _)| score > best_score) {
+                best = Some((score, index));
+            }
+        }
+        best.map(|(_, index)| index)
+    }
+
+    /// Deepens one move at a time up to `max_depth`, searching the best move
+    /// so far first, so running out of budget still leaves the best move found.
+    fn best_move(&self, board: &Board, max_depth: u8) -> Option<Move> {
+        let mut moves = all_available_moves(board);
+        for depth in 1..=max_depth.max(1) {
+            if let Some(best) = self.best_root_move(board, &moves, depth) {
+                moves[..=best].rotate_right(1);
+            }
+            if self.exhausted() {
+                break;
+            }
+        }
+        moves.into_iter().next()
+    }
+}
+
+impl AI for SearchAI {
+    fn next_move(&mut self, board: &Board) -> Option<Move> {
+        self.next_move_within(board, &Budget::default(), &AtomicBool::new(false))
+    }
+
+    fn next_move_within(&mut self, board: &Board, budget: &Budget, stop: &AtomicBool) -> Option<Move> {
+        Search::new(&self.rules, budget, stop).best_move(board, budget.depth.unwrap_or(self.depth))
+    }
+
+    fn feedback(&mut self, _outcome: &GameOutcome, _played_as: &Species) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn best_move_under(rules: Rules, position: &str, depth: u8) -> String {
+        let board: Board = position.parse().unwrap();
+        SearchAI::new(depth, rules).next_move(&board).unwrap().to_string()
+    }
+
+    fn best_move(position: &str, depth: u8) -> String {
+        best_move_under(Rules::default(), position, depth)
+    }
+
+    #[test]
+    fn next_move_should_take_immediate_win_for_either_side() {
+        assert_eq!(best_move("e3 b2,d2,g3,h2 w", 2), "e3f2");
+        assert_eq!(best_move("h8 a1,c1,e1,h6 s", 2), "h6g7");
+    }
+
+    #[test]
+    fn next_move_should_block_wolf_from_breaking_through() {
+        // any other move lets the wolf through via f2 and g1
+        assert_eq!(best_move("g3 b2,d2,e1,h2 s", 4), "e1f2");
+    }
+
+    #[test]
+    fn next_move_should_follow_the_rules_it_was_given() {
+        // c7b6 leaves the sheep without a move
+        let position = "c7 a5,d8,e7,f8 w";
+        let sheep_draw = Rules {
+            sheep_without_moves: crate::game::NoMovesRule::Draw,
+            ..Rules::default()
+        };
+
+        assert_eq!(best_move(position, 2), "c7b6");
+        assert_ne!(best_move_under(sheep_draw, position, 2), "c7b6");
+    }
+
+    #[test]
+    fn next_move_within_should_still_answer_when_stopped() {
+        let stop = AtomicBool::new(true);
+
+        let mv = SearchAI::new(8, Rules::default()).next_move_within(&Board::default(), &Budget::default(), &stop);
+
+        assert!(mv.is_some());
+    }
+
+    /// Searches the start position up to a depth of 60 within `budget`,
+    /// giving the move found and how many positions were visited.
+    fn search_within(budget: &Budget) -> (Option<Move>, u64) {
+        let rules = Rules::default();
+        let stop = AtomicBool::new(false);
+        let search = Search::new(&rules, budget, &stop);
+        let mv = search.best_move(&Board::default(), budget.depth.unwrap_or(60));
+        (mv, search.nodes.get())
+    }
+
+    #[test]
+    fn best_move_should_keep_to_the_node_budget() {
+        let (mv, nodes) = search_within(&Budget::nodes(1000));
+
+        assert!(mv.is_some());
+        assert_eq!(nodes, 1000);
+    }
+
+    #[test]
+    fn best_move_should_keep_to_the_depth_budget() {
+        let budget = Budget {
+            depth: Some(1),
+            ..Budget::default()
+        };
+
+        let (mv, nodes) = search_within(&budget);
+
+        // one position after each of the wolf's two opening moves
+        assert!(mv.is_some());
+        assert_eq!(nodes, 2);
+    }
+
+    #[test]
+    fn best_move_should_still_answer_once_the_movetime_is_spent() {
+        let (mv, nodes) = search_within(&Budget::movetime(Duration::ZERO));
+
+        assert!(mv.is_some());
+        assert_eq!(nodes, 0);
+    }
+
+    #[test]
+    fn next_move_within_should_find_wins_in_shallow_iterations() {
+        let board: Board = "g3 b2,d2,e1,h2 s".parse().unwrap();
+        let budget = Budget {
+            depth: Some(4),
+            ..Budget::nodes(100_000)
+        };
+
+        let mv = SearchAI::new(60, Rules::default()).next_move_within(&board, &budget, &AtomicBool::new(false));
+
+        assert_eq!(mv.unwrap().to_string(), "e1f2");
+    }
+}
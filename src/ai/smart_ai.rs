@@ -1,6 +1,6 @@
 use rand::random;
 
-use crate::game::{Species, Board, Move, movement::all_available_wolf_moves};
+use crate::game::{Species, Board, GameOutcome, Move, movement::all_available_wolf_moves};
 
 //non-learning just move down as wolf as much as possible
 #[derive(Debug)]
@@ -14,7 +14,7 @@ impl super::AI for SmartAI {
         }
     }
 
-    fn feedback(&mut self, _won: bool) {
+    fn feedback(&mut self, _outcome: &GameOutcome, _played_as: &Species) {
         
     }
 }
@@ -24,14 +24,14 @@ impl SmartAI {
         let possible_moves = all_available_wolf_moves(&board.wolf, &board.sheeps);
         let moves_down: Vec<_> = possible_moves
             .iter()
+            .filter(|m| m.to.y() > board.wolf.y())
             .cloned()
-            .filter(|m| m.to.y > board.wolf.y)
             .collect();
         let move_to_choose_from = if !moves_down.is_empty() { &moves_down } else { &possible_moves };
         if random::<bool>() {
-            move_to_choose_from.first().map(|x| x.clone())
+            move_to_choose_from.first().cloned()
         } else {
-            move_to_choose_from.last().map(|x| x.clone())
+            move_to_choose_from.last().cloned()
         }
     }
 }
\ No newline at end of file
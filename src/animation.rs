@@ -0,0 +1,36 @@
+use std::time::{Duration, Instant};
+
+use wolf_and_sheep::game::Move;
+
+/// How often animations are redrawn.
+pub const FRAME: Duration = Duration::from_millis(16);
+
+/// A move shown as its pin sliding from the source to the destination square.
+#[derive(Debug, Clone)]
+pub struct Animation {
+    pub mv: Move,
+    started: Instant,
+    duration: Duration,
+}
+
+impl Animation {
+    pub fn new(mv: Move, duration: Duration) -> Self {
+        Self {
+            mv,
+            started: Instant::now(),
+            duration,
+        }
+    }
+
+    /// Share of the way the pin has gone, from 0 to 1.
+    pub fn progress(&self, now: Instant) -> f32 {
+        if self.duration.is_zero() {
+            return 1.;
+        }
+        (now.saturating_duration_since(self.started).as_secs_f32() / self.duration.as_secs_f32()).min(1.)
+    }
+
+    pub fn is_done(&self, now: Instant) -> bool {
+        self.progress(now) >= 1.
+    }
+}
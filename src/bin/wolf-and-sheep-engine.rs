@@ -0,0 +1,13 @@
+use std::io;
+
+use wolf_and_sheep::{ai::AITypes, protocol};
+
+fn main() -> io::Result<()> {
+    let ai_type = match std::env::args().nth(1) {
+        Some(name) => name
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?,
+        None => AITypes::Smart,
+    };
+    protocol::serve(ai_type, io::stdin().lock(), io::stdout().lock())
+}
@@ -0,0 +1,165 @@
+use std::{fmt, time::Duration};
+
+use wolf_and_sheep::{ai::{AITypes, Budget}, game::{Species, TimeControl}};
+
+const SEARCH_DEPTHS: [u8; 4] = [2, 4, 6, 8];
+const MOVETIMES_MS: [u64; 4] = [100, 500, 1000, 3000];
+const NODE_BUDGETS: [u64; 2] = [10_000, 100_000];
+/// Base minutes and increment seconds of the offered time controls.
+const TIME_CONTROLS: [(u64, u64); 4] = [(1, 0), (3, 2), (5, 0), (10, 5)];
+
+/// Who plays a side, as offered in the setup row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ControlChoice {
+    Player,
+    Computer(AITypes),
+    /// Index into the remembrance AIs trained in learning mode.
+    Trained(usize),
+}
+
+impl ControlChoice {
+    pub fn can_play(&self, species: &Species) -> bool {
+        match self {
+            ControlChoice::Player => true,
+            ControlChoice::Computer(ai_type) => ai_type.can_play(species),
+            ControlChoice::Trained(_) => AITypes::Remembrance.can_play(species),
+        }
+    }
+}
+
+impl fmt::Display for ControlChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ControlChoice::Player => write!(f, "Player"),
+            ControlChoice::Computer(AITypes::Random) => write!(f, "Random AI"),
+            ControlChoice::Computer(AITypes::Smart) => write!(f, "Smart AI"),
+            ControlChoice::Computer(AITypes::Remembrance) => write!(f, "Untrained remembrance AI"),
+            ControlChoice::Computer(AITypes::Search { depth }) => write!(f, "Search AI, depth {depth}"),
+            ControlChoice::Computer(AITypes::External(command_line)) => write!(f, "External: {command_line}"),
+            ControlChoice::Trained(index) => write!(f, "Trained remembrance AI #{}", index + 1),
+        }
+    }
+}
+
+pub struct SideChoices {
+    pub wolf: ControlChoice,
+    pub sheep: ControlChoice,
+    /// Thinking budget of a computer wolf.
+    pub wolf_budget: Budget,
+    pub sheep_budget: Budget,
+    /// How many trained remembrance AIs can be picked.
+    pub trained: usize,
+    /// AI passed on the command line, offered to both sides.
+    pub extra: Option<AITypes>,
+}
+
+impl SideChoices {
+    pub fn new(extra: Option<AITypes>) -> Self {
+        Self {
+            wolf: ControlChoice::Player,
+            sheep: ControlChoice::Player,
+            wolf_budget: Budget::default(),
+            sheep_budget: Budget::default(),
+            trained: 0,
+            extra,
+        }
+    }
+
+    pub fn of(&self, species: &Species) -> &ControlChoice {
+        match species {
+            Species::Wolf => &self.wolf,
+            Species::Sheep => &self.sheep,
+        }
+    }
+
+    pub fn of_mut(&mut self, species: &Species) -> &mut ControlChoice {
+        match species {
+            Species::Wolf => &mut self.wolf,
+            Species::Sheep => &mut self.sheep,
+        }
+    }
+
+    pub fn budget(&self, species: &Species) -> &Budget {
+        match species {
+            Species::Wolf => &self.wolf_budget,
+            Species::Sheep => &self.sheep_budget,
+        }
+    }
+
+    pub fn budget_mut(&mut self, species: &Species) -> &mut Budget {
+        match species {
+            Species::Wolf => &mut self.wolf_budget,
+            Species::Sheep => &mut self.sheep_budget,
+        }
+    }
+
+    /// Swaps everything picked for the wolf with what was picked for the sheep.
+    pub fn swap(&mut self) {
+        std::mem::swap(&mut self.wolf, &mut self.sheep);
+        std::mem::swap(&mut self.wolf_budget, &mut self.sheep_budget);
+    }
+
+    pub fn budget_options() -> Vec<Budget> {
+        let movetimes = MOVETIMES_MS.map(|ms| Budget::movetime(Duration::from_millis(ms)));
+        std::iter::once(Budget::default())
+            .chain(movetimes)
+            .chain(NODE_BUDGETS.map(Budget::nodes))
+            .collect()
+    }
+
+    pub fn options(&self, species: &Species) -> Vec<ControlChoice> {
+        let mut computers: Vec<_> = [AITypes::Random, AITypes::Smart, AITypes::Remembrance]
+            .into_iter()
+            .chain(SEARCH_DEPTHS.map(|depth| AITypes::Search { depth }))
+            .collect();
+        // an AI given on the command line may well be one of the usual ones
+        if let Some(extra) = self.extra.clone().filter(|extra| !computers.contains(extra)) {
+            computers.push(extra);
+        }
+        std::iter::once(ControlChoice::Player)
+            .chain(computers.into_iter().map(ControlChoice::Computer))
+            .chain((0..self.trained).map(ControlChoice::Trained))
+            .filter(|choice| choice.can_play(species))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_should_list_a_usual_ai_given_as_extra_once() {
+        let choices = SideChoices::new(Some(AITypes::Random));
+
+        let options = choices.options(&Species::Wolf);
+
+        let random = ControlChoice::Computer(AITypes::Random);
+        assert_eq!(options.iter().filter(|choice| **choice == random).count(), 1);
+    }
+}
+
+/// Time control for the next game, `None` playing without clocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ClockChoice(pub Option<TimeControl>);
+
+impl ClockChoice {
+    pub fn options() -> Vec<ClockChoice> {
+        let timed = TIME_CONTROLS.map(|(minutes, increment)| {
+            ClockChoice(Some(TimeControl::new(
+                Duration::from_secs(minutes * 60),
+                Duration::from_secs(increment),
+            )))
+        });
+        std::iter::once(ClockChoice(None)).chain(timed).collect()
+    }
+}
+
+impl fmt::Display for ClockChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(control) => write!(f, "{control}"),
+            None => write!(f, "No clock"),
+        }
+    }
+}
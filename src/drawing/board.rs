@@ -1,16 +1,55 @@
-use super::GraphicMsg;
-use crate::game::{physics::*, Board, Coord, Species};
+use super::{physics::*, GraphicMsg};
+use crate::{
+    ai::state_is_lost_for_sheep,
+    sprite::Sprites,
+    theme::{Palette, Theme},
+    game::{
+        movement::{all_available_wolf_moves, legal_destinations},
+        Board, Coord, Move, Species, BOARD_SIZE,
+    },
+};
 use iced::{
-    canvas::{event::Status, Frame, Path, Program},
-    Color, Point, Rectangle, Size,
+    alignment::{Horizontal, Vertical},
+    canvas::{event::Status, Event, Frame, Path, Program, Stroke, Text},
+    mouse, Color, Point, Rectangle, Size, Vector,
 };
 
 /// radius to block's width ratio
 const RADIUS_RATIO: f32 = 0.35;
 const SELECTED_RADIUS_RATIO: f32 = 0.4;
+const DESTINATION_RADIUS_RATIO: f32 = 0.12;
+/// sprite size to block's width ratio
+const SPRITE_RATIO: f32 = 0.9;
+
+/// A pin being dragged from `from`, currently under the cursor at `at`.
+#[derive(Debug, Clone)]
+struct Dragging {
+    from: Coord,
+    at: Point,
+}
+
+/// Drag in progress, kept across redraws like other widget states.
+#[derive(Debug, Default)]
+pub struct DragState {
+    dragging: Option<Dragging>,
+}
 
-pub struct BoardGraphic {
+pub struct BoardGraphic<'a> {
     board: Board,
+    /// `None` for a board that can't be played on.
+    drag: Option<&'a mut DragState>,
+    /// Square of a recently refused move.
+    illegal_click: Option<Coord>,
+    /// Move to show sliding in, with how far along it is from 0 to 1.
+    animation: Option<(Move, f32)>,
+    last_move: Option<Move>,
+    show_threats: bool,
+    /// Clicks edit squares instead of moving pins.
+    editing: bool,
+    palette: Palette,
+    sprites: Sprites,
+    /// Shown from the wolf's side.
+    flipped: bool,
 }
 
 fn current_mover_matches_selected(board: &Board, selected: &Coord) -> bool {
@@ -24,20 +63,106 @@ fn current_mover_matches_selected(board: &Board, selected: &Coord) -> bool {
     false
 }
 
-impl BoardGraphic {
-    pub fn new(board: Board) -> Self {
-        Self { board }
+impl<'a> BoardGraphic<'a> {
+    /// Board played on by dragging or clicking pins, unless `drag` is `None`.
+    pub fn new(board: Board, drag: Option<&'a mut DragState>, illegal_click: Option<Coord>) -> Self {
+        Self {
+            board,
+            drag,
+            illegal_click,
+            animation: None,
+            last_move: None,
+            show_threats: false,
+            editing: false,
+            flipped: false,
+            palette: Palette::default(),
+            sprites: Sprites::default(),
+        }
+    }
+
+    /// Board that only displays a position, e.g. a finished or replayed game.
+    pub fn read_only(board: Board) -> Self {
+        Self {
+            board,
+            drag: None,
+            illegal_click: None,
+            animation: None,
+            last_move: None,
+            show_threats: false,
+            editing: false,
+            flipped: false,
+            palette: Palette::default(),
+            sprites: Sprites::default(),
+        }
+    }
+
+    /// Board whose squares are set up one click at a time.
+    pub fn editor(board: Board) -> Self {
+        Self {
+            editing: true,
+            ..Self::read_only(board)
+        }
+    }
+
+    /// Shows `mv`, already made on the board, `progress` of the way from its source.
+    pub fn animating(self, animation: Option<(Move, f32)>) -> Self {
+        Self { animation, ..self }
+    }
+
+    /// Draws in the colors and sprites of `theme`.
+    pub fn themed(self, theme: &Theme) -> Self {
+        Self {
+            palette: theme.palette,
+            sprites: theme.sprites.clone(),
+            ..self
+        }
+    }
+
+    /// Highlights the squares `last_move` went from and to.
+    pub fn last_move(self, last_move: Option<Move>) -> Self {
+        Self { last_move, ..self }
+    }
+
+    /// Marks where the wolf can go next, and which of those squares break the sheep line.
+    pub fn show_threats(self, show_threats: bool) -> Self {
+        Self {
+            show_threats,
+            ..self
+        }
+    }
+
+    /// Turns the board around, so the wolf's side is at the bottom.
+    pub fn flipped(self, flipped: bool) -> Self {
+        Self { flipped, ..self }
     }
 
-    fn handle_mouse(&self, bounds: &Rectangle, mouse: Point) -> (Status, Option<GraphicMsg>) {
-        let block = bounds.width / 8f32;
-        let radius = block * RADIUS_RATIO;
+    fn squares(&self, block: f32) -> Squares {
+        Squares {
+            block,
+            flipped: self.flipped,
+        }
+    }
+
+    fn dragging(&self) -> Option<&Dragging> {
+        self.drag.as_ref().and_then(|drag| drag.dragging.as_ref())
+    }
+
+    /// Picks up a pin of the side to move, or moves the selected pin
+    /// to the clicked square for click-click moves.
+    fn handle_press(&mut self, squares: Squares, mouse: Point) -> (Status, Option<GraphicMsg>) {
+        let radius = squares.block * RADIUS_RATIO;
         let colliding = self.board.iter().find(|c| {
-            let mid_point = coord_to_midpoint(c, block);
+            let mid_point = squares.midpoint(c);
             distance_between(&mid_point, &mouse) <= radius
         });
         if let Some(selected) = colliding {
             if current_mover_matches_selected(&self.board, selected) {
+                if let Some(drag) = &mut self.drag {
+                    drag.dragging = Some(Dragging {
+                        from: selected.clone(),
+                        at: mouse,
+                    });
+                }
                 return (
                     Status::Captured,
                     Some(GraphicMsg::PinSelected(selected.clone())),
@@ -45,18 +170,39 @@ impl BoardGraphic {
             }
         }
         if self.board.selected.is_some() {
-            let coord = point_to_coord(&mouse, bounds.width / 8f32);
-            return (Status::Captured, Some(GraphicMsg::PinMoved(coord)));
+            if let Some(coord) = squares.coord_at(&mouse) {
+                return (Status::Captured, Some(GraphicMsg::PinMoved(coord)));
+            }
         }
         (Status::Ignored, None)
     }
+
+    /// Drops a dragged pin. Dropping it back where it came from leaves it
+    /// selected, so the move can still be finished with a second click.
+    fn handle_release(&mut self, squares: Squares, mouse: Point) -> (Status, Option<GraphicMsg>) {
+        let Some(dragging) = self.drag.as_mut().and_then(|drag| drag.dragging.take()) else {
+            return (Status::Ignored, None);
+        };
+        match squares.coord_at(&mouse) {
+            // an illegal drop is refused by the app, leaving the pin where it was
+            Some(to) if to != dragging.from => (Status::Captured, Some(GraphicMsg::PinMoved(to))),
+            _ => (Status::Captured, None),
+        }
+    }
 }
 
-fn board(frame: &mut Frame, block: f32) {
+fn board(frame: &mut Frame, squares: Squares, palette: &Palette, sprites: &Sprites) {
+    let block = squares.block;
+    // a board image isn't turned around, so a flipped board is drawn plain
+    if let (Some(sprite), false) = (&sprites.board, squares.flipped) {
+        let side = block * 8.;
+        sprite.draw(frame, Point::new(side / 2., side / 2.), side);
+        return;
+    }
     let mut white = true;
     for x in 0i16..8 {
         for y in 0..8 {
-            let color = if white { Color::WHITE } else { Color::BLACK };
+            let color = if white { palette.light_square } else { palette.dark_square };
             white = !white;
             let top_left = top_left_point(x, y, block);
             frame.fill_rectangle(top_left, Size::new(block, block), color);
@@ -65,41 +211,149 @@ fn board(frame: &mut Frame, block: f32) {
     }
 }
 
-fn put_circle_in(frame: &mut Frame, coord: &Coord, ratio: f32, block: f32, color: &Color) {
-    let center = coord_to_midpoint(coord, block);
-    let circle = Path::circle(center, block * ratio);
+fn put_circle_in(frame: &mut Frame, coord: &Coord, ratio: f32, squares: Squares, color: &Color) {
+    let center = squares.midpoint(coord);
+    let circle = Path::circle(center, squares.block * ratio);
     frame.fill(&circle, *color)
 }
 
-fn pawns(frame: &mut Frame, block: f32, board: &Board) {
-    let wolf_color = Color::from_rgb(0.8, 0.5, 0.2);
-    let sheep_color = Color::from_rgb(1., 1., 0.);
+fn shade_square(frame: &mut Frame, squares: Squares, coord: &Coord, color: Color) {
+    let block = squares.block;
+    frame.fill_rectangle(squares.top_left(coord), Size::new(block, block), color);
+}
+
+fn last_move(frame: &mut Frame, squares: Squares, mv: &Move, palette: &Palette) {
+    shade_square(frame, squares, &mv.from, palette.last_move);
+    shade_square(frame, squares, &mv.to, palette.last_move);
+}
+
+/// Squares the wolf reaches next turn, in red where the sheep could no longer stop it.
+fn threats(frame: &mut Frame, squares: Squares, board: &Board, palette: &Palette) {
+    for mv in all_available_wolf_moves(&board.wolf, &board.sheeps) {
+        let color = if state_is_lost_for_sheep(&board.sheeps, &mv.to) {
+            palette.breakthrough
+        } else {
+            palette.threat
+        };
+        shade_square(frame, squares, &mv.to, color);
+    }
+}
+
+fn destinations(frame: &mut Frame, squares: Squares, board: &Board, palette: &Palette) {
+    if let Some(selected) = &board.selected {
+        for destination in legal_destinations(board, selected) {
+            put_circle_in(frame, &destination, DESTINATION_RADIUS_RATIO, squares, &palette.destination);
+        }
+    }
+}
+
+fn illegal_click(frame: &mut Frame, squares: Squares, coord: &Coord, palette: &Palette) {
+    let block = squares.block;
+    let square = Path::rectangle(squares.top_left(coord), Size::new(block, block));
+    let stroke = Stroke::default()
+        .with_color(palette.illegal_click)
+        .with_width(block * 0.08);
+    frame.stroke(&square, stroke);
+}
+
+fn pawns(
+    frame: &mut Frame,
+    squares: Squares,
+    board: &Board,
+    dragging: Option<&Dragging>,
+    animation: Option<&(Move, f32)>,
+    palette: &Palette,
+    sprites: &Sprites,
+) {
     if let Some(selected) = &board.selected {
-        let selected_color = Color::from_rgb(1., 0., 0.);
         put_circle_in(
             frame,
             selected,
             SELECTED_RADIUS_RATIO,
-            block,
-            &selected_color,
+            squares,
+            &palette.selected,
         );
     }
-    put_circle_in(frame, &board.wolf, RADIUS_RATIO, block, &wolf_color);
-    for sheep in &board.sheeps {
-        put_circle_in(frame, sheep, RADIUS_RATIO, block, &sheep_color);
+    for pin in board.iter() {
+        let center = match (dragging, animation) {
+            (Some(dragging), _) if dragging.from == *pin => dragging.at,
+            (_, Some((mv, progress))) if mv.to == *pin => interpolate(
+                squares.midpoint(&mv.from),
+                squares.midpoint(&mv.to),
+                *progress,
+            ),
+            _ => squares.midpoint(pin),
+        };
+        let block = squares.block;
+        let (sprite, color) = match *pin == board.wolf {
+            true => (&sprites.wolf, palette.wolf),
+            false => (&sprites.sheep, palette.sheep),
+        };
+        match sprite {
+            Some(sprite) => sprite.draw(frame, center, block * SPRITE_RATIO),
+            None => frame.fill(&Path::circle(center, block * RADIUS_RATIO), color),
+        }
+    }
+}
+
+/// File letters below the board and rank numbers left of it, as in move notation.
+fn labels(frame: &mut Frame, squares: Squares, palette: &Palette) {
+    let block = squares.block;
+    for index in 0..BOARD_SIZE {
+        // the square in column or row `index`, whichever way the board is turned
+        let Ok(square) = Coord::new(index, BOARD_SIZE - 1 - index) else {
+            continue;
+        };
+        let shown = squares.shown(&square).to_string();
+        let (file, rank) = shown.split_at(1);
+        let offset = f32::from(index) * block + block / 2.;
+        let label = |content: &str, position| Text {
+            content: content.to_owned(),
+            position,
+            color: palette.label,
+            size: block * 0.3,
+            horizontal_alignment: Horizontal::Center,
+            vertical_alignment: Vertical::Center,
+            ..Default::default()
+        };
+        frame.fill_text(label(file, Point::new(offset, 8. * block + block / 4.)));
+        frame.fill_text(label(rank, Point::new(-block / 4., f32::from(BOARD_SIZE - 1 - index) * block + block / 2.)));
     }
 }
 
-impl Program<GraphicMsg> for BoardGraphic {
+/// Cursor position relative to the top left corner of the board, even
+/// outside of the canvas so drags dropped off the board still end.
+fn board_position(cursor: iced::canvas::Cursor, bounds: &Rectangle, origin: Vector) -> Option<Point> {
+    cursor.position().map(|position| Point {
+        x: position.x - bounds.x - origin.x,
+        y: position.y - bounds.y - origin.y,
+    })
+}
+
+impl Program<GraphicMsg> for BoardGraphic<'_> {
     fn draw(
         &self,
         bounds: iced::Rectangle,
         _cursor: iced::canvas::Cursor,
     ) -> Vec<iced::canvas::Geometry> {
         let mut frame = Frame::new(bounds.size());
-        let block = bounds.width / 8f32;
-        board(&mut frame, block);
-        pawns(&mut frame, block, &self.board);
+        let (block, origin) = fit_board(bounds.size());
+        let squares = self.squares(block);
+        frame.translate(origin);
+        let palette = &self.palette;
+        board(&mut frame, squares, palette, &self.sprites);
+        labels(&mut frame, squares, palette);
+        if let Some(mv) = &self.last_move {
+            last_move(&mut frame, squares, mv, palette);
+        }
+        if self.show_threats {
+            threats(&mut frame, squares, &self.board, palette);
+        }
+        destinations(&mut frame, squares, &self.board, palette);
+        if let Some(coord) = &self.illegal_click {
+            illegal_click(&mut frame, squares, coord, palette);
+        }
+        pawns(&mut frame, squares, &self.board, self.dragging(), self.animation.as_ref(), palette, &self.sprites);
         vec![frame.into_geometry()]
     }
 
@@ -109,11 +363,40 @@ impl Program<GraphicMsg> for BoardGraphic {
         bounds: iced::Rectangle,
         cursor: iced::canvas::Cursor,
     ) -> (iced::canvas::event::Status, Option<GraphicMsg>) {
-        if let iced::canvas::Event::Mouse(iced::mouse::Event::ButtonPressed(_)) = event {
-            let mut position = cursor.position().unwrap();
-            position.y -= 100.;
-            return self.handle_mouse(&bounds, position);
+        let (block, origin) = fit_board(bounds.size());
+        let squares = self.squares(block);
+        let Some(position) = board_position(cursor, &bounds, origin) else {
+            return (Status::Ignored, None);
+        };
+        if self.editing {
+            return match event {
+                Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                    match squares.coord_at(&position) {
+                        Some(coord) => (Status::Captured, Some(GraphicMsg::EditSquare(coord))),
+                        None => (Status::Ignored, None),
+                    }
+                }
+                _ => (Status::Ignored, None),
+            };
+        }
+        let Some(drag) = &mut self.drag else {
+            return (Status::Ignored, None);
+        };
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                self.handle_press(squares, position)
+            }
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => match &mut drag.dragging {
+                Some(dragging) => {
+                    dragging.at = position;
+                    (Status::Captured, None)
+                }
+                None => (Status::Ignored, None),
+            },
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                self.handle_release(squares, position)
+            }
+            _ => (Status::Ignored, None),
         }
-        (Status::Ignored, None)
     }
 }
@@ -4,22 +4,31 @@ use iced::{Element, Column, Length, Button, button::State, Text, Alignment};
 pub enum ChoosingGraphicMsg {
     Play,
     Learn,
+    Replay,
+    Edit,
 }
 
-static mut PLAY_BUTTON_STATE: Option<State> = None;
-static mut LEARN_BUTTON_STATE: Option<State> = None;
+#[derive(Default)]
+pub struct ChoosingState {
+    play: State,
+    learn: State,
+    replay: State,
+    edit: State,
+}
 
-pub fn choosing<'a>() -> Element<'a, ChoosingGraphicMsg> {
-    let play_state = unsafe { PLAY_BUTTON_STATE.get_or_insert_with(State::new) };
-    let learn_state = unsafe { LEARN_BUTTON_STATE.get_or_insert_with(State::new) };
+pub fn choosing(state: &mut ChoosingState) -> Element<'_, ChoosingGraphicMsg> {
     Column::new()
         .width(Length::Fill)
         .width(Length::Fill)
         .align_items(Alignment::Center)
         .spacing(10)
-        .push(Button::new(play_state, Text::new("Play"))
+        .push(Button::new(&mut state.play, Text::new("Play"))
             .on_press(ChoosingGraphicMsg::Play))
-        .push(Button::new(learn_state, Text::new("Learn"))
+        .push(Button::new(&mut state.learn, Text::new("Learn"))
             .on_press(ChoosingGraphicMsg::Learn))
+        .push(Button::new(&mut state.replay, Text::new("Replay saved game"))
+            .on_press(ChoosingGraphicMsg::Replay))
+        .push(Button::new(&mut state.edit, Text::new("Edit position"))
+            .on_press(ChoosingGraphicMsg::Edit))
         .into()
-}
\ No newline at end of file
+}
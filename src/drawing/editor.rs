@@ -0,0 +1,69 @@
+use iced::{button::State, pick_list, Alignment, Button, Checkbox, Column, Element, Length, PickList, Row, Text};
+
+use crate::game::{editor::{EditTool, PositionError}, Board};
+
+#[derive(Clone)]
+pub enum EditorGraphicMsg {
+    Tool(EditTool),
+    ToggleSide,
+    Clear,
+    Reset,
+    Flip(bool),
+    Play,
+    Analyse,
+    Menu,
+}
+
+#[derive(Default)]
+pub struct EditorState {
+    tool: pick_list::State<EditTool>,
+    side: State,
+    clear: State,
+    reset: State,
+    play: State,
+    analyse: State,
+    menu: State,
+}
+
+fn start_button<'a>(state: &'a mut State, label: &str, msg: EditorGraphicMsg, valid: bool) -> Button<'a, EditorGraphicMsg> {
+    let button = Button::new(state, Text::new(label));
+    match valid {
+        true => button.on_press(msg),
+        false => button,
+    }
+}
+
+/// Controls above the edited board, which can only be played from once `check` passes.
+pub fn controls<'a>(state: &'a mut EditorState, board: &Board, tool: EditTool, check: &Result<(), PositionError>, flipped: bool) -> Element<'a, EditorGraphicMsg> {
+    let side = format!("{:?} to move", board.currently_moving);
+    let verdict = match check {
+        Ok(()) => "Ready to play".to_owned(),
+        Err(e) => format!("Can't start: {e}"),
+    };
+    let editing = Row::new()
+        .spacing(10)
+        .push(PickList::new(&mut state.tool, &EditTool::ALL[..], Some(tool), EditorGraphicMsg::Tool))
+        .push(Button::new(&mut state.side, Text::new(side))
+            .on_press(EditorGraphicMsg::ToggleSide))
+        .push(Button::new(&mut state.clear, Text::new("Clear"))
+            .on_press(EditorGraphicMsg::Clear))
+        .push(Button::new(&mut state.reset, Text::new("Start position"))
+            .on_press(EditorGraphicMsg::Reset))
+        .push(Checkbox::new(flipped, "Flip board", EditorGraphicMsg::Flip));
+    let starting = Row::new()
+        .spacing(10)
+        .push(start_button(&mut state.play, "Play", EditorGraphicMsg::Play, check.is_ok()))
+        .push(start_button(&mut state.analyse, "Analyse", EditorGraphicMsg::Analyse, check.is_ok()))
+        .push(Button::new(&mut state.menu, Text::new("Menu"))
+            .on_press(EditorGraphicMsg::Menu));
+    Column::new()
+        .width(Length::Fill)
+        .align_items(Alignment::Center)
+        .spacing(10)
+        .padding(10)
+        .push(Text::new("Click squares to place or erase pins with the tool picked below").size(16))
+        .push(editing)
+        .push(Text::new(verdict).size(16))
+        .push(starting)
+        .into()
+}
@@ -0,0 +1,52 @@
+use iced::{button::State, Alignment, Button, Column, Element, Length, Row, Text};
+
+use crate::game::GameOutcome;
+
+#[derive(Clone)]
+pub enum GameOverGraphicMsg {
+    Rematch,
+    SwapSides,
+    Review,
+    Save,
+    Menu,
+}
+
+#[derive(Default)]
+pub struct GameOverState {
+    rematch: State,
+    swap_sides: State,
+    review: State,
+    save: State,
+    menu: State,
+}
+
+pub fn summary<'a>(outcome: Option<&GameOutcome>, moves: usize) -> Element<'a, GameOverGraphicMsg> {
+    let result = match outcome {
+        Some(outcome) => format!("{outcome}"),
+        None => "Game over".to_owned(),
+    };
+    Column::new()
+        .width(Length::Fill)
+        .align_items(Alignment::Center)
+        .spacing(5)
+        .push(Text::new(result))
+        .push(Text::new(format!("{moves} moves played")).size(16))
+        .into()
+}
+
+pub fn buttons(state: &mut GameOverState) -> Element<'_, GameOverGraphicMsg> {
+    Row::new()
+        .width(Length::Fill)
+        .spacing(10)
+        .push(Button::new(&mut state.rematch, Text::new("Rematch"))
+            .on_press(GameOverGraphicMsg::Rematch))
+        .push(Button::new(&mut state.swap_sides, Text::new("Swap sides"))
+            .on_press(GameOverGraphicMsg::SwapSides))
+        .push(Button::new(&mut state.review, Text::new("Review"))
+            .on_press(GameOverGraphicMsg::Review))
+        .push(Button::new(&mut state.save, Text::new("Save"))
+            .on_press(GameOverGraphicMsg::Save))
+        .push(Button::new(&mut state.menu, Text::new("Menu"))
+            .on_press(GameOverGraphicMsg::Menu))
+        .into()
+}
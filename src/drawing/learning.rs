@@ -1,4 +1,4 @@
-use iced::{Element, Column, Length, Text, ProgressBar, Alignment};
+use iced::{Element, Column, Length, ProgressBar, Alignment};
 
 use crate::ai::learning::LearningProgress;
 
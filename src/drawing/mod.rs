@@ -1,27 +1,115 @@
 mod board;
 mod setup;
 mod choosing;
+mod editor;
+mod game_over;
 mod learning;
+mod move_list;
+mod pace;
+mod physics;
+mod replay;
 
-use iced::{Canvas, Column, Element, Length};
+use iced::{Alignment, Canvas, Column, Element, Length, Row, Text};
 
-use crate::{game::{Board, Controls, Coord, Species}, mode::GameMode, ai::learning::LearningProgress};
+use crate::{ai::Budget, choices::{ClockChoice, ControlChoice, SideChoices}, game::{editor::{check_position, EditTool}, Board, Clocks, Coord, GameOutcome, GameRecord, Move, Species}, mode::GameMode, pace::Pace, theme::Themes, ai::learning::LearningProgress};
 
-use self::choosing::choosing;
+use self::{
+    board::DragState,
+    move_list::MoveListState,
+    choosing::{choosing, ChoosingState},
+    editor::{EditorGraphicMsg, EditorState},
+    game_over::{GameOverGraphicMsg, GameOverState},
+    replay::{ReplayGraphicMsg, ReplayState},
+    setup::SetupState,
+};
 
-#[derive(Debug)]
+#[derive(Default)]
+pub struct Widgets {
+    choosing: ChoosingState,
+    editor: EditorState,
+    setup: SetupState,
+    drag: DragState,
+    move_list: MoveListState,
+    game_over: GameOverState,
+    replay: ReplayState,
+}
+
+#[derive(Debug, Clone)]
 pub enum GraphicMsg {
     PinSelected(Coord),
     PinMoved(Coord),
-    ControlChanged(Species),
+    ControlChanged(Species, ControlChoice),
+    BudgetChanged(Species, Budget),
+    TimeControlChanged(ClockChoice),
     ModeSelected(GameMode),
+    Resign,
+    Rematch,
+    SwapSides,
+    AIDelayChanged(u32),
+    AnimationChanged(u32),
+    ThreatsToggled(bool),
+    /// Look at the position after this many moves of the game being played.
+    ShowPly(usize),
+    ToggleAutoplay,
+    AutoplaySpeedChanged(u32),
+    LeaveReplay,
+    SaveGame,
+    LoadGame,
+    /// Edit the square clicked in the board editor.
+    EditSquare(Coord),
+    EditToolChanged(EditTool),
+    EditorSideToggled,
+    EditorCleared,
+    EditorReset,
+    /// Start from the edited position, with both sides moved by hand to analyse it.
+    StartFromEdited { analyse: bool },
+    ThemeSelected(String),
+    FlipToggled(bool),
+    TogglePause,
+    Step,
+}
+
+/// Everything from the app state that a frame shows.
+pub struct Scene<'s> {
+    pub board: &'s Board,
+    pub record: &'s GameRecord,
+    pub choices: &'s SideChoices,
+    pub pace: &'s Pace,
+    pub clocks: Option<&'s Clocks>,
+    pub time_control: ClockChoice,
+    pub last_outcome: Option<&'s GameOutcome>,
+    pub illegal_click: Option<&'s Coord>,
+    /// Move sliding into place, with how far along it is from 0 to 1.
+    pub animation: Option<(Move, f32)>,
+    pub show_threats: bool,
+    /// Earlier position looked at during a game, as the number of moves into it.
+    pub viewing: Option<usize>,
+    /// Saved game being replayed instead of the last one played.
+    pub replayed: Option<&'s GameRecord>,
+    pub autoplay: &'s Pace,
+    /// Position set up in the board editor.
+    pub edited: &'s Board,
+    pub edit_tool: EditTool,
+    pub themes: &'s Themes,
+    /// Flip board ticked, turning the board from the side it faces by default.
+    pub flipped: bool,
+    /// Board shown with the wolf's side at the bottom, as it is when the
+    /// player only plays the wolf, unless flipped back.
+    pub wolf_at_bottom: bool,
+    pub mode: &'s GameMode,
+    pub progress: &'s LearningProgress,
+    /// Something to tell the player, like an external AI failing or a game saved.
+    pub notice: Option<&'s str>,
 }
 
-pub fn view<'a>(board: &Board, controls: &Controls, mode: &GameMode, progress: &LearningProgress) -> Element<'a, GraphicMsg> {
-    match mode {
-        GameMode::ChoosingMode => choosing_view(),
-        GameMode::Playing => playing_view(board, controls),
-        GameMode::Learning => learning_progress_view(progress),
+pub fn view<'a>(widgets: &'a mut Widgets, scene: Scene) -> Element<'a, GraphicMsg> {
+    match scene.mode {
+        GameMode::ChoosingMode => choosing_view(&mut widgets.choosing, scene.notice),
+        GameMode::Playing => playing_view(&mut widgets.setup, &mut widgets.drag, &mut widgets.move_list, &scene),
+        GameMode::Learning => learning_progress_view(scene.progress),
+        GameMode::GameOver => game_over_view(&mut widgets.game_over, &scene),
+        GameMode::Replay { ply } => replay_view(&mut widgets.replay, &mut widgets.move_list, &scene, *ply),
+        GameMode::Editing => editor_view(&mut widgets.editor, &scene),
     }
 }
 
@@ -29,17 +117,123 @@ fn learning_progress_view<'a>(progress: &LearningProgress) -> Element<'a, Graphi
     learning::learning_progress(progress).map(|_| unreachable!())
 }
 
-fn choosing_view<'a>() -> Element<'a, GraphicMsg> {
-    choosing().map(|m| match m {
+fn choosing_view<'a>(state: &'a mut ChoosingState, notice: Option<&str>) -> Element<'a, GraphicMsg> {
+    let menu = choosing(state).map(|m| match m {
         choosing::ChoosingGraphicMsg::Play => GraphicMsg::ModeSelected(GameMode::Playing),
         choosing::ChoosingGraphicMsg::Learn => GraphicMsg::ModeSelected(GameMode::Learning),
-    })
+        choosing::ChoosingGraphicMsg::Replay => GraphicMsg::LoadGame,
+        choosing::ChoosingGraphicMsg::Edit => GraphicMsg::ModeSelected(GameMode::Editing),
+    });
+    Column::new()
+        .spacing(10)
+        .align_items(Alignment::Center)
+        .push(menu)
+        .push(Text::new(notice.unwrap_or_default()).size(16))
+        .into()
 }
 
-fn playing_view<'a>(board: &Board, controls: &Controls) -> Element<'a, GraphicMsg> {
-    let board_graphics = Canvas::new(self::board::BoardGraphic::new(board.clone()))
+fn playing_view<'a>(setup: &'a mut SetupState, drag: &'a mut DragState, move_list: &'a mut MoveListState, scene: &Scene) -> Element<'a, GraphicMsg> {
+    // only a player picks up pins, and only on their own turn
+    let drag = match scene.choices.of(&scene.board.currently_moving) {
+        ControlChoice::Player => Some(drag),
+        _ => {
+            *drag = DragState::default();
+            None
+        }
+    };
+    let record = scene.record;
+    let board_graphics = match scene.viewing {
+        Some(ply) => read_only_board(&record.position_at(ply), last_move_at(record, ply), scene),
+        None => Canvas::new(self::board::BoardGraphic::new(scene.board.clone(), drag, scene.illegal_click.cloned()).animating(scene.animation.clone())
+            .themed(scene.themes.current())
+            .flipped(scene.wolf_at_bottom)
+            .last_move(record.moves.last().cloned())
+            .show_threats(scene.show_threats))
+            .height(Length::Fill)
+            .width(Length::Fill)
+            .into(),
+    };
+    let shown = scene.viewing.unwrap_or(record.moves.len());
+    let moves = move_list::panel(move_list, record, shown).map(GraphicMsg::ShowPly);
+    let control = setup::view(setup, scene);
+    let mut column = Column::new().push(control);
+    if let Some(notice) = scene.notice {
+        column = column.push(Text::new(notice));
+    }
+    column.push(Row::new().push(board_graphics).push(moves)).into()
+}
+
+/// Move that led to the position after `ply` moves.
+fn last_move_at(record: &GameRecord, ply: usize) -> Option<&Move> {
+    ply.checked_sub(1).and_then(|last| record.moves.get(last))
+}
+
+fn read_only_board<'a>(board: &Board, last_move: Option<&Move>, scene: &Scene) -> Element<'a, GraphicMsg> {
+    let graphic = self::board::BoardGraphic::read_only(board.clone())
+        .themed(scene.themes.current())
+        .flipped(scene.wolf_at_bottom)
+        .animating(scene.animation.clone())
+        .last_move(last_move.cloned())
+        .show_threats(scene.show_threats);
+    Canvas::new(graphic)
         .height(Length::Fill)
-        .width(Length::Fill);
-    let control = setup::view(controls);
-    Column::new().push(control).push(board_graphics).into()
+        .width(Length::Fill)
+        .into()
+}
+
+fn game_over_view<'a>(state: &'a mut GameOverState, scene: &Scene) -> Element<'a, GraphicMsg> {
+    let to_graphic_msg = |m| match m {
+        GameOverGraphicMsg::Rematch => GraphicMsg::Rematch,
+        GameOverGraphicMsg::SwapSides => GraphicMsg::SwapSides,
+        GameOverGraphicMsg::Review => GraphicMsg::ModeSelected(GameMode::Replay { ply: 0 }),
+        GameOverGraphicMsg::Save => GraphicMsg::SaveGame,
+        GameOverGraphicMsg::Menu => GraphicMsg::ModeSelected(GameMode::ChoosingMode),
+    };
+    Column::new()
+        .spacing(10)
+        .push(game_over::summary(scene.last_outcome, scene.record.moves.len()).map(to_graphic_msg))
+        .push(game_over::buttons(state).map(to_graphic_msg))
+        .push(Text::new(scene.notice.unwrap_or_default()).size(16))
+        .push(read_only_board(scene.board, scene.record.moves.last(), scene))
+        .into()
+}
+
+fn replay_view<'a>(state: &'a mut ReplayState, move_list: &'a mut MoveListState, scene: &Scene, ply: usize) -> Element<'a, GraphicMsg> {
+    let record = scene.replayed.unwrap_or(scene.record);
+    let controls = replay::controls(state, ply, record.moves.len(), scene.autoplay, scene.flipped).map(|m| match m {
+        ReplayGraphicMsg::Show(ply) => GraphicMsg::ModeSelected(GameMode::Replay { ply }),
+        ReplayGraphicMsg::ToggleAutoplay => GraphicMsg::ToggleAutoplay,
+        ReplayGraphicMsg::SpeedChanged(delay_ms) => GraphicMsg::AutoplaySpeedChanged(delay_ms),
+        ReplayGraphicMsg::Flip(flipped) => GraphicMsg::FlipToggled(flipped),
+        ReplayGraphicMsg::Back => GraphicMsg::LeaveReplay,
+    });
+    Column::new()
+        .spacing(10)
+        .push(controls)
+        .push(
+            Row::new()
+                .push(read_only_board(&record.position_at(ply), last_move_at(record, ply), scene))
+                .push(move_list::panel(move_list, record, ply).map(|ply| GraphicMsg::ModeSelected(GameMode::Replay { ply }))),
+        )
+        .into()
+}
+
+fn editor_view<'a>(state: &'a mut EditorState, scene: &Scene) -> Element<'a, GraphicMsg> {
+    let check = check_position(scene.edited, &scene.record.rules);
+    let controls = editor::controls(state, scene.edited, scene.edit_tool, &check, scene.flipped).map(|m| match m {
+        EditorGraphicMsg::Tool(tool) => GraphicMsg::EditToolChanged(tool),
+        EditorGraphicMsg::ToggleSide => GraphicMsg::EditorSideToggled,
+        EditorGraphicMsg::Clear => GraphicMsg::EditorCleared,
+        EditorGraphicMsg::Reset => GraphicMsg::EditorReset,
+        EditorGraphicMsg::Flip(flipped) => GraphicMsg::FlipToggled(flipped),
+        EditorGraphicMsg::Play => GraphicMsg::StartFromEdited { analyse: false },
+        EditorGraphicMsg::Analyse => GraphicMsg::StartFromEdited { analyse: true },
+        EditorGraphicMsg::Menu => GraphicMsg::ModeSelected(GameMode::ChoosingMode),
+    });
+    Column::new()
+        .push(controls)
+        .push(Canvas::new(self::board::BoardGraphic::editor(scene.edited.clone()).themed(scene.themes.current()).flipped(scene.wolf_at_bottom))
+            .height(Length::Fill)
+            .width(Length::Fill))
+        .into()
 }
@@ -0,0 +1,49 @@
+use iced::{button, scrollable, Button, Column, Element, Length, Scrollable, Text};
+
+use crate::game::{GameRecord, Species};
+
+pub const MOVE_LIST_WIDTH: u16 = 120;
+
+#[derive(Default)]
+pub struct MoveListState {
+    scroll: scrollable::State,
+    latest: button::State,
+    moves: Vec<button::State>,
+}
+
+/// Numbered moves of `record`, each showing the position after it when clicked.
+/// `shown` is the ply on the board, marked in the list.
+pub fn panel<'a>(state: &'a mut MoveListState, record: &GameRecord, shown: usize) -> Element<'a, usize> {
+    let numbered = record.numbered_moves();
+    state.moves.resize_with(numbered.len(), Default::default);
+    let mut latest = Button::new(&mut state.latest, Text::new("Latest").size(14)).padding(3);
+    if shown != numbered.len() {
+        latest = latest.on_press(numbered.len());
+    }
+    let moves = numbered
+        .into_iter()
+        .zip(state.moves.iter_mut())
+        .enumerate()
+        .fold(Column::new().spacing(2), |column, (index, ((number, species, mv), button))| {
+            let ply = index + 1;
+            let marker = if ply == shown { ">" } else { " " };
+            let label = match species {
+                Species::Wolf => format!("{marker}{number}. {mv}"),
+                Species::Sheep => format!("{marker}{number}... {mv}"),
+            };
+            column.push(
+                Button::new(button, Text::new(label).size(14))
+                    .padding(2)
+                    .width(Length::Fill)
+                    .on_press(ply),
+            )
+        });
+    Column::new()
+        .width(Length::Units(MOVE_LIST_WIDTH))
+        .height(Length::Fill)
+        .spacing(5)
+        .padding(5)
+        .push(latest)
+        .push(Scrollable::new(&mut state.scroll).height(Length::Fill).push(moves))
+        .into()
+}
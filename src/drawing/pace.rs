@@ -0,0 +1,63 @@
+use iced::{button, slider, Alignment, Button, Column, Element, Row, Slider, Text};
+
+use crate::pace::Pace;
+
+/// Bounds of the delay between computer moves, in milliseconds.
+pub const MIN_AI_DELAY: u32 = 50;
+pub const MAX_AI_DELAY: u32 = 2000;
+pub const MAX_ANIMATION: u32 = 1000;
+
+#[derive(Clone, Debug)]
+pub enum PaceGraphicMsg {
+    DelayChanged(u32),
+    AnimationChanged(u32),
+    TogglePause,
+    Step,
+}
+
+#[derive(Default)]
+pub struct PaceState {
+    delay: slider::State,
+    animation: slider::State,
+    pause: button::State,
+    step: button::State,
+}
+
+/// Speed and animation sliders plus pause and single-step buttons for computer turns.
+pub fn controls<'a>(state: &'a mut PaceState, pace: &Pace) -> Element<'a, PaceGraphicMsg> {
+    let Pace {
+        delay_ms,
+        paused,
+        animation_ms,
+        ..
+    } = *pace;
+    let pause_label = if paused { "Resume" } else { "Pause" };
+    let mut step = Button::new(&mut state.step, Text::new("Step").size(14)).padding(5);
+    if paused {
+        step = step.on_press(PaceGraphicMsg::Step);
+    }
+    Column::new()
+        .align_items(Alignment::Center)
+        .spacing(5)
+        .push(Text::new(format!("AI move every {delay_ms} ms")).size(14))
+        .push(
+            Slider::new(&mut state.delay, MIN_AI_DELAY..=MAX_AI_DELAY, delay_ms, PaceGraphicMsg::DelayChanged)
+                .step(MIN_AI_DELAY),
+        )
+        .push(Text::new(format!("Moves slide for {animation_ms} ms")).size(14))
+        .push(
+            Slider::new(&mut state.animation, 0..=MAX_ANIMATION, animation_ms, PaceGraphicMsg::AnimationChanged)
+                .step(50),
+        )
+        .push(
+            Row::new()
+                .spacing(5)
+                .push(
+                    Button::new(&mut state.pause, Text::new(pause_label).size(14))
+                        .padding(5)
+                        .on_press(PaceGraphicMsg::TogglePause),
+                )
+                .push(step),
+        )
+        .into()
+}
@@ -0,0 +1,134 @@
+use iced::{Point, Size, Vector};
+
+use crate::game::Coord;
+
+pub fn distance_between(a: &Point, b: &Point) -> f32 {
+    ((a.x - b.x).powf(2.) + (a.y - b.y).powf(2.)).sqrt()
+}
+
+/// Blocks taken by the board plus its coordinate labels, which run
+/// half a block wide along its left and bottom edges.
+const LABELLED_BLOCKS: f32 = 8.5;
+
+/// Square size and top left corner of the largest labelled board fitting
+/// in `size`, centred in whichever direction has room to spare.
+pub fn fit_board(size: Size) -> (f32, Vector) {
+    let block = size.width.min(size.height) / LABELLED_BLOCKS;
+    let side = block * LABELLED_BLOCKS;
+    let origin = Vector::new(
+        (size.width - side) / 2. + block / 2.,
+        (size.height - side) / 2.,
+    );
+    (block, origin)
+}
+
+/// Where squares are drawn: how big they are, and whether the board is
+/// turned around, showing the wolf's side at the bottom.
+#[derive(Debug, Clone, Copy)]
+pub struct Squares {
+    pub block: f32,
+    pub flipped: bool,
+}
+
+impl Squares {
+    /// Square drawn where `coord` would be unflipped, and the other way round.
+    pub fn shown(&self, coord: &Coord) -> Coord {
+        match self.flipped {
+            true => coord.rotated(),
+            false => coord.clone(),
+        }
+    }
+
+    pub fn midpoint(&self, coord: &Coord) -> Point {
+        coord_to_midpoint(&self.shown(coord), self.block)
+    }
+
+    pub fn top_left(&self, coord: &Coord) -> Point {
+        let shown = self.shown(coord);
+        top_left_point(shown.x(), shown.y(), self.block)
+    }
+
+    /// Square under `point`, `None` off the board or on a light square.
+    pub fn coord_at(&self, point: &Point) -> Option<Coord> {
+        point_to_coord(point, self.block).map(|coord| self.shown(&coord))
+    }
+}
+
+pub fn coord_to_midpoint(coord: &Coord, block: f32) -> Point {
+    Point {
+        x: coord.x() as f32 * block + block / 2.,
+        y: coord.y() as f32 * block + block / 2.,
+    }
+}
+
+/// Point `t` of the way from `a` to `b`.
+pub fn interpolate(a: Point, b: Point, t: f32) -> Point {
+    Point::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t)
+}
+
+/// `None` for points off the board or on a light square.
+pub fn point_to_coord(point: &Point, block: f32) -> Option<Coord> {
+    if point.x < 0. || point.y < 0. {
+        return None;
+    }
+    // float to int casts saturate, so far away clicks end up out of bounds
+    let x = (point.x / block).floor() as u8;
+    let y = (point.y / block).floor() as u8;
+    Coord::new(x, y).ok()
+}
+
+pub fn top_left_point<N>(x: N, y: N, block: f32) -> Point
+where
+    N: Into<f32>,
+{
+    Point::new(x.into() * block, y.into() * block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dark_squares() -> impl Iterator<Item = Coord> {
+        (0..8).flat_map(|x| (0..8).filter_map(move |y| Coord::new(x, y).ok()))
+    }
+
+    #[test]
+    fn fit_board_should_centre_the_board_in_a_wide_window() {
+        let (block, origin) = fit_board(Size::new(850., 425.));
+
+        assert_eq!(block, 50.);
+        // the labels take the half block left of the board
+        assert_eq!(origin, Vector::new(212.5 + 25., 0.));
+    }
+
+    #[test]
+    fn fit_board_should_centre_the_board_in_a_tall_window() {
+        let (block, origin) = fit_board(Size::new(425., 625.));
+
+        assert_eq!(block, 50.);
+        assert_eq!(origin, Vector::new(25., 100.));
+    }
+
+    #[test]
+    fn coord_at_should_find_no_square_on_the_label_margin() {
+        for flipped in [false, true] {
+            let squares = Squares { block: 50., flipped };
+            // left of a8 and below a1, where rank and file labels go
+            assert_eq!(squares.coord_at(&Point::new(-10., 25.)), None);
+            assert_eq!(squares.coord_at(&Point::new(75., 410.)), None);
+        }
+    }
+
+    #[test]
+    fn coord_at_should_undo_midpoint_either_way_up() {
+        for flipped in [false, true] {
+            let squares = Squares { block: 50., flipped };
+            for coord in dark_squares() {
+                assert_eq!(squares.coord_at(&squares.midpoint(&coord)), Some(coord));
+            }
+        }
+        let d8 = "d8".parse().unwrap();
+        let flipped = Squares { block: 50., flipped: true };
+        assert_eq!(flipped.midpoint(&d8), Point::new(225., 375.));
+    }
+}
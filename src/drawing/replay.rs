@@ -0,0 +1,71 @@
+use iced::{button::State, slider, Alignment, Button, Checkbox, Column, Element, Length, Row, Slider, Text};
+
+use super::pace::{MAX_AI_DELAY, MIN_AI_DELAY};
+use crate::pace::Pace;
+
+#[derive(Clone)]
+pub enum ReplayGraphicMsg {
+    Show(usize),
+    ToggleAutoplay,
+    SpeedChanged(u32),
+    Flip(bool),
+    Back,
+}
+
+#[derive(Default)]
+pub struct ReplayState {
+    first: State,
+    previous: State,
+    next: State,
+    last: State,
+    timeline: slider::State,
+    autoplay: State,
+    speed: slider::State,
+    back: State,
+}
+
+fn step_button<'a>(state: &'a mut State, label: &str, target: Option<usize>) -> Button<'a, ReplayGraphicMsg> {
+    let button = Button::new(state, Text::new(label));
+    match target {
+        Some(ply) => button.on_press(ReplayGraphicMsg::Show(ply)),
+        None => button,
+    }
+}
+
+/// Controls for stepping through `moves` plies, currently showing `ply`,
+/// and for playing them back at the pace of `autoplay`, unless it is paused.
+pub fn controls<'a>(state: &'a mut ReplayState, ply: usize, moves: usize, autoplay: &Pace, flipped: bool) -> Element<'a, ReplayGraphicMsg> {
+    let at_start = (ply > 0).then_some(0);
+    let at_end = (ply < moves).then_some(moves);
+    let stepping = Row::new()
+        .width(Length::Fill)
+        .align_items(Alignment::Center)
+        .spacing(10)
+        .push(step_button(&mut state.first, "|<", at_start))
+        .push(step_button(&mut state.previous, "<", ply.checked_sub(1)))
+        .push(Text::new(format!("Move {ply} / {moves}")))
+        .push(step_button(&mut state.next, ">", at_end.map(|_| ply + 1)))
+        .push(step_button(&mut state.last, ">|", at_end))
+        .push(Button::new(&mut state.back, Text::new("Back")).on_press(ReplayGraphicMsg::Back));
+    let autoplay_label = if autoplay.paused { "Play" } else { "Pause" };
+    let playback = Row::new()
+        .width(Length::Fill)
+        .align_items(Alignment::Center)
+        .spacing(10)
+        .push(Button::new(&mut state.autoplay, Text::new(autoplay_label).size(14))
+            .padding(5)
+            .on_press(ReplayGraphicMsg::ToggleAutoplay))
+        .push(Text::new(format!("every {} ms", autoplay.delay_ms)).size(14))
+        .push(Slider::new(&mut state.speed, MIN_AI_DELAY..=MAX_AI_DELAY, autoplay.delay_ms, ReplayGraphicMsg::SpeedChanged)
+            .step(MIN_AI_DELAY))
+        .push(Checkbox::new(flipped, "Flip board", ReplayGraphicMsg::Flip).text_size(14).size(14));
+    let mut column = Column::new().spacing(5).padding(5).push(stepping);
+    // a slider over an empty range has nothing to scrub
+    if moves > 0 {
+        let to_u32 = |ply: usize| u32::try_from(ply).unwrap_or(u32::MAX);
+        column = column.push(Slider::new(&mut state.timeline, 0..=to_u32(moves), to_u32(ply), |ply| {
+            ReplayGraphicMsg::Show(ply as usize)
+        }));
+    }
+    column.push(playback).into()
+}
@@ -1,28 +1,124 @@
-use iced::{Alignment, Checkbox, Column, Element, Length, Row};
+use std::time::Duration;
 
-use super::GraphicMsg;
-use crate::game::{Control, Controls, Species};
+use iced::{button, pick_list, Alignment, Button, Checkbox, Column, Element, Length, PickList, Row, Text};
 
-fn species_column<'a>(control: &Control, species: Species) -> Column<'a, GraphicMsg> {
-    let is_ai_controlled = matches!(control, Control::Computer);
-    let label = format!("{:?} AI controlled", species);
+use super::{
+    pace::{self, PaceGraphicMsg, PaceState},
+    GraphicMsg, Scene,
+};
+use crate::{ai::Budget, choices::{ClockChoice, ControlChoice, SideChoices}, game::{Clocks, Species}};
+
+/// Height of the row above the board, fixed so the board keeps its size
+/// while budget pickers come and go.
+pub const SETUP_HEIGHT: u16 = 260;
+
+#[derive(Default)]
+pub struct SetupState {
+    resign: button::State,
+    time_control: pick_list::State<ClockChoice>,
+    theme: pick_list::State<String>,
+    pace: PaceState,
+    wolf_control: pick_list::State<ControlChoice>,
+    sheep_control: pick_list::State<ControlChoice>,
+    wolf_budget: pick_list::State<Budget>,
+    sheep_budget: pick_list::State<Budget>,
+}
+
+/// Minutes and seconds, with tenths once time gets short.
+fn format_clock(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    match secs {
+        0..=9 => format!("0:0{secs}.{}", remaining.subsec_millis() / 100),
+        _ => format!("{}:{:02}", secs / 60, secs % 60),
+    }
+}
+
+fn species_column<'a>(
+    control: &'a mut pick_list::State<ControlChoice>,
+    budget: &'a mut pick_list::State<Budget>,
+    choices: &SideChoices,
+    clocks: Option<&Clocks>,
+    species: Species,
+) -> Column<'a, GraphicMsg> {
+    let label = format!("{:?} played by", species);
+    let selected = choices.of(&species).clone();
+    let is_computer = selected != ControlChoice::Player;
+    let control_species = species.clone();
+    let mut column = Column::new()
+        .width(Length::FillPortion(1))
+        .align_items(Alignment::Center)
+        .spacing(5)
+        .push(Text::new(label).size(16));
+    if let Some(clocks) = clocks {
+        column = column.push(Text::new(format_clock(clocks.remaining(&species))).size(20));
+    }
+    let column = column
+        .push(PickList::new(control, choices.options(&species), Some(selected), move |choice| {
+            GraphicMsg::ControlChanged(control_species.clone(), choice)
+        }).text_size(14));
+    if !is_computer {
+        return column;
+    }
+    let selected_budget = choices.budget(&species).clone();
+    column
+        .push(Text::new("Thinking budget").size(14))
+        .push(PickList::new(budget, SideChoices::budget_options(), Some(selected_budget), move |budget| {
+            GraphicMsg::BudgetChanged(species.clone(), budget)
+        }).text_size(14))
+}
+
+fn status_column<'a>(
+    resign: &'a mut button::State,
+    time_control: &'a mut pick_list::State<ClockChoice>,
+    theme: &'a mut pick_list::State<String>,
+    pace_state: &'a mut PaceState,
+    scene: &Scene,
+) -> Column<'a, GraphicMsg> {
+    let status = format!("{:?} to move", scene.board.currently_moving);
+    let resign = Button::new(resign, Text::new("Resign").size(14)).padding(5);
+    let player_plays = [Species::Wolf, Species::Sheep]
+        .iter()
+        .any(|species| matches!(scene.choices.of(species), ControlChoice::Player));
+    let resign = match player_plays {
+        true => resign.on_press(GraphicMsg::Resign),
+        false => resign,
+    };
+    let pace_controls = pace::controls(pace_state, scene.pace).map(|m| match m {
+        PaceGraphicMsg::DelayChanged(delay_ms) => GraphicMsg::AIDelayChanged(delay_ms),
+        PaceGraphicMsg::AnimationChanged(animation_ms) => GraphicMsg::AnimationChanged(animation_ms),
+        PaceGraphicMsg::TogglePause => GraphicMsg::TogglePause,
+        PaceGraphicMsg::Step => GraphicMsg::Step,
+    });
     Column::new()
         .width(Length::FillPortion(1))
         .align_items(Alignment::Center)
-        .push(Checkbox::new(is_ai_controlled, label, move |_| {
-            GraphicMsg::ControlChanged(species.clone())
-        }))
+        .spacing(10)
+        .push(Text::new(status).size(16))
+        .push(
+            Row::new()
+                .spacing(5)
+                .align_items(Alignment::Center)
+                .push(resign)
+                // applies to the next game once this one has started
+                .push(PickList::new(time_control, ClockChoice::options(), Some(scene.time_control), GraphicMsg::TimeControlChanged).text_size(14)),
+        )
+        .push(
+            Row::new()
+                .spacing(10)
+                .push(Checkbox::new(scene.show_threats, "Show wolf threats", GraphicMsg::ThreatsToggled).text_size(14).size(14))
+                .push(Checkbox::new(scene.flipped, "Flip board", GraphicMsg::FlipToggled).text_size(14).size(14)),
+        )
+        .push(PickList::new(theme, scene.themes.names(), Some(scene.themes.current().name.clone()), GraphicMsg::ThemeSelected).text_size(14))
+        .push(pace_controls)
 }
 
-pub fn view<'a>(controls: &Controls) -> Element<'a, GraphicMsg> {
+pub fn view<'a>(state: &'a mut SetupState, scene: &Scene) -> Element<'a, GraphicMsg> {
     Row::new()
         .width(Length::Fill)
-        .height(Length::Units(100))
+        .height(Length::Units(SETUP_HEIGHT))
         .align_items(Alignment::Center)
-        .push(species_column(&controls.wolf_controlled_by, Species::Wolf))
-        .push(species_column(
-            &controls.sheep_controlled_by,
-            Species::Sheep,
-        ))
+        .push(species_column(&mut state.wolf_control, &mut state.wolf_budget, scene.choices, scene.clocks, Species::Wolf))
+        .push(status_column(&mut state.resign, &mut state.time_control, &mut state.theme, &mut state.pace, scene))
+        .push(species_column(&mut state.sheep_control, &mut state.sheep_budget, scene.choices, scene.clocks, Species::Sheep))
         .into()
 }
@@ -2,15 +2,26 @@ use std::iter::once;
 
 use super::coord::Coord;
 
+/// Serialized in [`super::notation`], so a deserialized board is checked like a parsed one.
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "String", try_from = "String")
+)]
 pub struct Board {
     pub wolf: Coord,
-    pub sheeps: [Coord; 4],
+    pub sheeps: Vec<Coord>,
     pub selected: Option<Coord>,
     pub currently_moving: Species,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "String", try_from = "String")
+)]
 pub struct Move {
     pub from: Coord,
     pub to: Coord,
@@ -22,24 +33,26 @@ impl Move {
     }
 }
 
-#[derive(Clone)]
-pub struct Controls {
-    pub wolf_controlled_by: Control,
-    pub sheep_controlled_by: Control,
-}
-
-#[derive(Clone, Debug)]
-pub enum Control {
-    Player,
-    Computer,
-}
-
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum Species {
     Wolf,
     Sheep,
 }
 
+impl Species {
+    pub fn opposite(&self) -> Species {
+        match self {
+            Species::Wolf => Species::Sheep,
+            Species::Sheep => Species::Wolf,
+        }
+    }
+}
+
 impl Board {
     pub fn iter(&self) -> impl Iterator<Item = &Coord> {
         self.sheeps.iter().chain(once(&self.wolf))
@@ -50,24 +63,15 @@ impl Board {
     }
 }
 
-impl Default for Controls {
-    fn default() -> Self {
-        Self {
-            wolf_controlled_by: Control::Player,
-            sheep_controlled_by: Control::Player,
-        }
-    }
-}
-
 impl Default for Board {
     fn default() -> Self {
         Self {
-            wolf: Coord { x: 3, y: 0 },
-            sheeps: [
-                Coord { x: 0, y: 7 },
-                Coord { x: 2, y: 7 },
-                Coord { x: 4, y: 7 },
-                Coord { x: 6, y: 7 },
+            wolf: Coord::new_unchecked(3, 0),
+            sheeps: vec![
+                Coord::new_unchecked(0, 7),
+                Coord::new_unchecked(2, 7),
+                Coord::new_unchecked(4, 7),
+                Coord::new_unchecked(6, 7),
             ],
             selected: None,
             currently_moving: Species::Wolf,
@@ -0,0 +1,103 @@
+use std::{fmt, time::Duration};
+
+use super::{GameOutcome, Species};
+
+/// Time each side starts with, and gets back after each of its moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeControl {
+    pub base: Duration,
+    pub increment: Duration,
+}
+
+impl TimeControl {
+    pub fn new(base: Duration, increment: Duration) -> Self {
+        Self { base, increment }
+    }
+}
+
+/// Written like in chess, minutes plus seconds of increment, e.g. `3+2`.
+impl fmt::Display for TimeControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let base = self.base.as_secs();
+        match base % 60 {
+            0 => write!(f, "{}+{}", base / 60, self.increment.as_secs()),
+            _ => write!(f, "{base}s+{}", self.increment.as_secs()),
+        }
+    }
+}
+
+/// Remaining time of both sides in a game played under a [`TimeControl`].
+#[derive(Debug, Clone)]
+pub struct Clocks {
+    pub control: TimeControl,
+    wolf: Duration,
+    sheep: Duration,
+}
+
+impl Clocks {
+    pub fn new(control: TimeControl) -> Self {
+        Self {
+            control,
+            wolf: control.base,
+            sheep: control.base,
+        }
+    }
+
+    pub fn remaining(&self, species: &Species) -> Duration {
+        match species {
+            Species::Wolf => self.wolf,
+            Species::Sheep => self.sheep,
+        }
+    }
+
+    fn remaining_mut(&mut self, species: &Species) -> &mut Duration {
+        match species {
+            Species::Wolf => &mut self.wolf,
+            Species::Sheep => &mut self.sheep,
+        }
+    }
+
+    /// Takes `elapsed` off the clock of `species`, which loses on time once it runs out.
+    #[must_use]
+    pub fn charge(&mut self, species: &Species, elapsed: Duration) -> Option<GameOutcome> {
+        let remaining = self.remaining_mut(species);
+        *remaining = remaining.saturating_sub(elapsed);
+        remaining.is_zero().then(|| GameOutcome::timeout(species))
+    }
+
+    /// Adds the increment after `species` made a move.
+    pub fn moved(&mut self, species: &Species) {
+        let increment = self.control.increment;
+        *self.remaining_mut(species) += increment;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn charge_should_flag_only_the_side_out_of_time() {
+        let mut clocks = Clocks::new(TimeControl::new(secs(60), secs(2)));
+
+        assert_eq!(clocks.charge(&Species::Wolf, secs(59)), None);
+        clocks.moved(&Species::Wolf);
+        assert_eq!(clocks.remaining(&Species::Wolf), secs(3));
+        assert_eq!(clocks.remaining(&Species::Sheep), secs(60));
+        assert_eq!(
+            clocks.charge(&Species::Wolf, secs(4)),
+            Some(GameOutcome::timeout(&Species::Wolf))
+        );
+    }
+
+    #[test]
+    fn time_control_should_display_like_chess() {
+        assert_eq!(TimeControl::new(secs(180), secs(2)).to_string(), "3+2");
+        assert_eq!(TimeControl::new(secs(30), secs(0)).to_string(), "30s+0");
+    }
+}
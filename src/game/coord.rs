@@ -1,11 +1,124 @@
+use std::fmt;
+
+/// Squares per board side.
+pub const BOARD_SIZE: u8 = 8;
+
+/// A dark, on-board square. Pieces only ever stand on dark squares,
+/// so any other coordinate is rejected at construction.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "String", try_from = "String")
+)]
 pub struct Coord {
-    pub x: u8,
-    pub y: u8,
+    x: u8,
+    y: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoordError {
+    OutOfBounds { x: u8, y: u8 },
+    LightSquare { x: u8, y: u8 },
+}
+
+impl fmt::Display for CoordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoordError::OutOfBounds { x, y } => write!(f, "({x}, {y}) is outside of the board"),
+            CoordError::LightSquare { x, y } => write!(f, "({x}, {y}) is a light square"),
+        }
+    }
+}
+
+impl std::error::Error for CoordError {}
+
+fn is_dark(x: u8, y: u8) -> bool {
+    (x + y) % 2 == 1
 }
 
 impl Coord {
-    pub fn new(x: u8, y: u8) -> Self {
+    pub fn new(x: u8, y: u8) -> Result<Self, CoordError> {
+        if x >= BOARD_SIZE || y >= BOARD_SIZE {
+            Err(CoordError::OutOfBounds { x, y })
+        } else if !is_dark(x, y) {
+            Err(CoordError::LightSquare { x, y })
+        } else {
+            Ok(Self { x, y })
+        }
+    }
+
+    /// For squares known to be valid at compile time, like the starting position.
+    pub(crate) const fn new_unchecked(x: u8, y: u8) -> Self {
         Self { x, y }
     }
+
+    pub fn x(&self) -> u8 {
+        self.x
+    }
+
+    pub fn y(&self) -> u8 {
+        self.y
+    }
+
+    /// Square `dx` columns and `dy` rows away, if it is still on the board.
+    pub fn offset(&self, dx: i8, dy: i8) -> Option<Coord> {
+        let x = self.x.checked_add_signed(dx)?;
+        let y = self.y.checked_add_signed(dy)?;
+        Coord::new(x, y).ok()
+    }
+
+    /// The up to four diagonal neighbours, which are all dark as well.
+    pub fn diagonals(&self) -> impl Iterator<Item = Coord> + '_ {
+        [(1, 1), (1, -1), (-1, 1), (-1, -1)]
+            .into_iter()
+            .filter_map(|(dx, dy)| self.offset(dx, dy))
+    }
+
+    /// Same square seen from the other side of the board, which is dark too.
+    pub fn rotated(&self) -> Coord {
+        Self {
+            x: BOARD_SIZE - 1 - self.x,
+            y: BOARD_SIZE - 1 - self.y,
+        }
+    }
+}
+
+impl TryFrom<(u8, u8)> for Coord {
+    type Error = CoordError;
+
+    fn try_from((x, y): (u8, u8)) -> Result<Self, Self::Error> {
+        Coord::new(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_should_accept_only_dark_squares_on_board() {
+        assert!(Coord::new(3, 0).is_ok());
+        assert!(Coord::new(0, 7).is_ok());
+        assert_eq!(Coord::new(0, 0), Err(CoordError::LightSquare { x: 0, y: 0 }));
+        assert_eq!(Coord::new(8, 1), Err(CoordError::OutOfBounds { x: 8, y: 1 }));
+        assert_eq!(Coord::try_from((1, 8)), Err(CoordError::OutOfBounds { x: 1, y: 8 }));
+    }
+
+    #[test]
+    fn diagonals_should_stay_on_board() {
+        let corner = Coord::new(7, 0).unwrap();
+        let center = Coord::new(3, 4).unwrap();
+
+        assert_eq!(corner.diagonals().collect::<Vec<_>>(), [Coord::new(6, 1).unwrap()]);
+        assert_eq!(center.diagonals().count(), 4);
+    }
+
+    #[test]
+    fn rotated_should_swap_opposite_corners() {
+        let corner = Coord::new(7, 0).unwrap();
+
+        assert_eq!(corner.rotated(), Coord::new(0, 7).unwrap());
+        assert_eq!(corner.rotated().rotated(), corner);
+    }
 }
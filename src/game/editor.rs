@@ -0,0 +1,138 @@
+use std::fmt;
+
+use super::{engine, Board, Coord, GameOutcome, Rules};
+
+/// Why an edited position can't be played from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PositionError {
+    NoSheep,
+    /// Two pins stand on this square.
+    Overlap(Coord),
+    /// The game would be over before the first move.
+    Decided(GameOutcome),
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionError::NoSheep => write!(f, "place at least one sheep"),
+            PositionError::Overlap(coord) => write!(f, "two pins on {coord}"),
+            PositionError::Decided(outcome) => write!(f, "already decided: {outcome}"),
+        }
+    }
+}
+
+impl std::error::Error for PositionError {}
+
+/// Whether a game can start from `board`. Pins always stand on dark
+/// squares, as [`Coord`] can't hold any other.
+pub fn check_position(board: &Board, rules: &Rules) -> Result<(), PositionError> {
+    if board.sheeps.is_empty() {
+        return Err(PositionError::NoSheep);
+    }
+    let pins: Vec<_> = board.iter().collect();
+    for (index, pin) in pins.iter().enumerate() {
+        if pins[index + 1..].contains(pin) {
+            return Err(PositionError::Overlap((*pin).clone()));
+        }
+    }
+    match engine::game_outcome(board, rules) {
+        Some(outcome) => Err(PositionError::Decided(outcome)),
+        None => Ok(()),
+    }
+}
+
+/// What a click in the board editor does to the square.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EditTool {
+    /// Puts a sheep on an empty square.
+    #[default]
+    Sheep,
+    /// Moves the wolf onto the square, taking the place of a sheep there.
+    Wolf,
+    /// Takes a sheep off. There is always exactly one wolf, so it can only be moved.
+    Erase,
+}
+
+impl EditTool {
+    pub const ALL: [EditTool; 3] = [EditTool::Sheep, EditTool::Wolf, EditTool::Erase];
+}
+
+impl fmt::Display for EditTool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EditTool::Sheep => write!(f, "Place sheep"),
+            EditTool::Wolf => write!(f, "Place wolf"),
+            EditTool::Erase => write!(f, "Erase sheep"),
+        }
+    }
+}
+
+/// Applies `tool` to the square at `coord`.
+pub fn edit_square(board: &mut Board, coord: Coord, tool: EditTool) {
+    let sheep = board.sheeps.iter().position(|sheep| *sheep == coord);
+    match (tool, sheep) {
+        (EditTool::Sheep, None) if coord != board.wolf => board.sheeps.push(coord),
+        (EditTool::Wolf, _) => {
+            if let Some(index) = sheep {
+                board.sheeps.remove(index);
+            }
+            board.wolf = coord;
+        }
+        (EditTool::Erase, Some(index)) => {
+            board.sheeps.remove(index);
+        }
+        _ => {}
+    }
+    board.selected = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{OutcomeReason, Species};
+
+    fn board(position: &str) -> Board {
+        position.parse().unwrap()
+    }
+
+    #[test]
+    fn edit_square_should_apply_the_tool() {
+        let mut edited = board("d8 a1 w");
+        let mut edit = |square: &str, tool| {
+            edit_square(&mut edited, square.parse().unwrap(), tool);
+            edited.to_string()
+        };
+
+        assert_eq!(edit("c1", EditTool::Sheep), "d8 a1,c1 w");
+        assert_eq!(edit("d8", EditTool::Sheep), "d8 a1,c1 w", "the wolf's square is taken");
+        assert_eq!(edit("a1", EditTool::Wolf), "a1 c1 w", "the wolf replaces the sheep");
+        assert_eq!(edit("a1", EditTool::Erase), "a1 c1 w", "the wolf stays");
+        edit("c1", EditTool::Erase);
+        assert!(edited.sheeps.is_empty());
+    }
+
+    #[test]
+    fn check_position_should_reject_unplayable_positions() {
+        let rules = Rules::default();
+        assert_eq!(check_position(&board("d8 a1,c1,e1,g1 s"), &rules), Ok(()));
+        assert_eq!(check_position(&board("d8 b2 w"), &rules), Ok(()));
+        // parsing already refuses overlaps, so only a board built by hand has them
+        let mut overlap = board("d8 a1 w");
+        overlap.sheeps.push("a1".parse().unwrap());
+        assert_eq!(
+            check_position(&overlap, &rules),
+            Err(PositionError::Overlap("a1".parse().unwrap()))
+        );
+        assert_eq!(
+            check_position(&board("d2 a5,c5 w"), &rules),
+            Err(PositionError::Decided(GameOutcome::win(
+                Species::Wolf,
+                OutcomeReason::WolfBrokeThrough
+            )))
+        );
+        let mut no_sheep = board("d8 a1 w");
+        no_sheep.sheeps.clear();
+        assert_eq!(check_position(&no_sheep, &rules), Err(PositionError::NoSheep));
+    }
+}
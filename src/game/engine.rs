@@ -1,90 +1,217 @@
-use crate::ai::AI;
-
 use super::{
-    board::{Control, Controls, Move, Species},
+    board::{Move, Species},
     movement::*,
-    Board,
+    Board, GameOutcome, GameRecord, NoMovesRule, OutcomeReason, Rules,
 };
 
-fn has_a_winner(board: &Board) -> Option<Species> {
-    if board.wolf.y >= board.sheeps.iter().map(|x| x.y).max().unwrap() {
-        Some(Species::Wolf)
-    } else if wolf_cant_move(board) {
-        Some(Species::Sheep)
-    } else {
-        None
+fn stuck_reason(species: &Species) -> OutcomeReason {
+    match species {
+        Species::Wolf => OutcomeReason::WolfTrapped,
+        Species::Sheep => OutcomeReason::SheepImmobilized,
     }
 }
 
-fn change_current_mover(board: &mut Board) {
-    board.currently_moving = match board.currently_moving {
-        Species::Wolf => Species::Sheep,
-        Species::Sheep => Species::Wolf,
+/// Whether the game is decided, judging the side to move by `rules` when it has no moves.
+pub fn game_outcome(board: &Board, rules: &Rules) -> Option<GameOutcome> {
+    // with no sheep left nothing stands between the wolf and the far side
+    let last_sheep_y = board.sheeps.iter().map(|x| x.y()).max();
+    if last_sheep_y.is_none_or(|y| board.wolf.y() >= y) {
+        return Some(GameOutcome::win(Species::Wolf, OutcomeReason::WolfBrokeThrough));
+    }
+    if !all_available_moves(board).is_empty() {
+        return None;
+    }
+    let mut opponent_to_move = board.clone();
+    change_current_mover(&mut opponent_to_move);
+    if all_available_moves(&opponent_to_move).is_empty() {
+        return Some(GameOutcome::draw(OutcomeReason::Stalemate));
     }
+    let stuck = &board.currently_moving;
+    match rules.without_moves(stuck) {
+        NoMovesRule::Pass => None,
+        NoMovesRule::Loss => Some(GameOutcome::win(stuck.opposite(), stuck_reason(stuck))),
+        NoMovesRule::Draw => Some(GameOutcome::draw(OutcomeReason::Stalemate)),
+    }
+}
+
+fn change_current_mover(board: &mut Board) {
+    board.currently_moving = board.currently_moving.opposite();
 }
 
 fn deselect_pin(board: &mut Board) {
     board.selected = None;
 }
 
+/// Hands the turn over when the side to move is stuck and the game goes on,
+/// which only happens under [`NoMovesRule::Pass`].
+fn pass_if_stuck(board: &mut Board, outcome: &Option<GameOutcome>) {
+    if outcome.is_none() && all_available_moves(board).is_empty() {
+        change_current_mover(board);
+    }
+}
+
 #[must_use]
-pub fn handle_move(board: &mut Board, mv: &Move) -> Option<Species> {
+pub fn handle_move(board: &mut Board, mv: &Move, rules: &Rules) -> Option<GameOutcome> {
     if valid_move(board, mv) {
         move_pin(board, mv);
         change_current_mover(board);
         deselect_pin(board);
-        has_a_winner(board)
+        let outcome = game_outcome(board, rules);
+        pass_if_stuck(board, &outcome);
+        outcome
     } else {
         None
     }
 }
 
-fn computer_moving(
-    current_mover: &Species,
-    wolf_control: &Control,
-    sheep_control: &Control,
-) -> bool {
-    if matches!(current_mover, Species::Wolf) {
-        matches!(wolf_control, Control::Computer)
-    } else {
-        matches!(sheep_control, Control::Computer)
+/// Like [`handle_move`], also appending the move to `record` when it is legal.
+#[must_use]
+pub fn handle_recorded_move(
+    board: &mut Board,
+    record: &mut GameRecord,
+    mv: &Move,
+) -> Option<GameOutcome> {
+    if valid_move(board, mv) {
+        record.push(mv.clone());
     }
+    handle_move(board, mv, &record.rules)
 }
 
-#[must_use]
-pub fn handle_tick(
-    board: &mut Board,
-    controls: &Controls,
-    ai: &mut Box<dyn AI>,
-) -> Option<Species> {
-    if computer_moving(
-        &board.currently_moving,
-        &controls.wolf_controlled_by,
-        &controls.sheep_controlled_by,
-    ) {
-        if let Some(mv) = ai.next_move(board) {
-            return handle_move(board, &mv);
-        } else {
-            change_current_mover(board);
+/// Starts over from `start`, keeping the rules of the last game. A side
+/// stuck from the start passes right away, so the game starts with the other.
+pub fn handle_new_game(board: &mut Board, record: &mut GameRecord, start: Board) {
+    *board = start;
+    let outcome = game_outcome(board, &record.rules);
+    pass_if_stuck(board, &outcome);
+    *record = GameRecord {
+        start: board.clone(),
+        rules: record.rules.clone(),
+        moves: Vec::new(),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(position: &str) -> Board {
+        position.parse().unwrap()
+    }
+
+    fn mv(notation: &str) -> Move {
+        notation.parse().unwrap()
+    }
+
+    fn rules(wolf_without_moves: NoMovesRule, sheep_without_moves: NoMovesRule) -> Rules {
+        Rules {
+            wolf_without_moves,
+            sheep_without_moves,
         }
     }
-    None
-}
 
-fn opposite(control: &Control) -> Control {
-    match control {
-        Control::Player => Control::Computer,
-        Control::Computer => Control::Player,
+    #[test]
+    fn handle_move_should_report_wolf_breaking_through() {
+        let mut board = board("e3 b2,d2,g3,h2 w");
+
+        let outcome = handle_move(&mut board, &mv("e3f2"), &Rules::default());
+
+        assert_eq!(
+            outcome,
+            Some(GameOutcome::win(Species::Wolf, OutcomeReason::WolfBrokeThrough))
+        );
     }
-}
 
-pub fn handle_control_change(controls: &mut Controls, species: Species) {
-    match species {
-        Species::Wolf => controls.wolf_controlled_by = opposite(&controls.wolf_controlled_by),
-        Species::Sheep => controls.sheep_controlled_by = opposite(&controls.sheep_controlled_by),
+    #[test]
+    fn handle_move_should_report_trapped_wolf() {
+        let mut board = board("h8 a1,c1,e1,h6 s");
+
+        let outcome = handle_move(&mut board, &mv("h6g7"), &Rules::default());
+
+        assert_eq!(
+            outcome,
+            Some(GameOutcome::win(Species::Sheep, OutcomeReason::WolfTrapped))
+        );
     }
-}
 
-pub fn handle_win(winner: Species, board: &mut Board) {
-    *board = Board::default();
+    #[test]
+    fn game_outcome_should_detect_immobilized_sheep_and_stalemate() {
+        let rules = Rules::default();
+        assert_eq!(game_outcome(&board("d8 a1,c1,e1,g1 w"), &rules), None);
+        assert_eq!(
+            game_outcome(&board("h8 a7,b8,d8,f8 s"), &rules),
+            Some(GameOutcome::win(Species::Wolf, OutcomeReason::SheepImmobilized))
+        );
+        assert_eq!(
+            game_outcome(&board("h8 b8,d8,f8,g7 w"), &rules),
+            Some(GameOutcome::draw(OutcomeReason::Stalemate))
+        );
+    }
+
+    #[test]
+    fn game_outcome_should_give_the_wolf_a_board_without_sheep() {
+        let mut no_sheep = board("d8 a1 s");
+        no_sheep.sheeps.clear();
+
+        assert_eq!(
+            game_outcome(&no_sheep, &Rules::default()),
+            Some(GameOutcome::win(Species::Wolf, OutcomeReason::WolfBrokeThrough))
+        );
+    }
+
+    #[test]
+    fn game_outcome_should_follow_no_moves_rules() {
+        let sheep_stuck = board("h8 a7,b8,d8,f8 s");
+        let wolf_trapped = board("h8 a1,c1,e1,g7 w");
+
+        let pass = rules(NoMovesRule::Pass, NoMovesRule::Pass);
+        assert_eq!(game_outcome(&sheep_stuck, &pass), None);
+        assert_eq!(game_outcome(&wolf_trapped, &pass), None);
+
+        let draw = rules(NoMovesRule::Draw, NoMovesRule::Draw);
+        assert_eq!(
+            game_outcome(&sheep_stuck, &draw),
+            Some(GameOutcome::draw(OutcomeReason::Stalemate))
+        );
+        assert_eq!(
+            game_outcome(&wolf_trapped, &Rules::default()),
+            Some(GameOutcome::win(Species::Sheep, OutcomeReason::WolfTrapped))
+        );
+    }
+
+    #[test]
+    fn handle_move_should_pass_for_stuck_side_under_pass_rule() {
+        let mut board = board("d8 f8,g7,h6,h8 w");
+        let pass = rules(NoMovesRule::Loss, NoMovesRule::Pass);
+
+        let outcome = handle_move(&mut board, &mv("d8c7"), &pass);
+
+        assert_eq!(outcome, None);
+        assert_eq!(board.currently_moving, Species::Wolf);
+    }
+
+    #[test]
+    fn handle_new_game_should_pass_for_side_stuck_at_the_start() {
+        let mut board = Board::default();
+        let mut record = GameRecord {
+            rules: rules(NoMovesRule::Loss, NoMovesRule::Pass),
+            ..Default::default()
+        };
+
+        handle_new_game(&mut board, &mut record, self::board("b8 f8,g7,h6,h8 s"));
+
+        assert_eq!(board.currently_moving, Species::Wolf);
+        assert_eq!(record.start.to_string(), board.to_string());
+    }
+
+    #[test]
+    fn handle_recorded_move_should_record_only_legal_moves() {
+        let mut board = Board::default();
+        let mut record = GameRecord::default();
+
+        let _ = handle_recorded_move(&mut board, &mut record, &mv("d8e5"));
+        let _ = handle_recorded_move(&mut board, &mut record, &mv("d8c7"));
+
+        assert_eq!(record.moves, [mv("d8c7")]);
+        assert_eq!(record.current().to_string(), board.to_string());
+    }
 }
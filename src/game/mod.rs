@@ -1,12 +1,19 @@
 mod board;
+mod clock;
 mod coord;
+pub mod editor;
 pub mod engine;
 pub mod movement;
-pub mod physics;
+pub mod notation;
+mod outcome;
+mod record;
+mod rules;
 
 pub use board::Board;
-pub use board::Control;
-pub use board::Controls;
 pub use board::Move;
 pub use board::Species;
-pub use coord::Coord;
+pub use clock::{Clocks, TimeControl};
+pub use coord::{Coord, CoordError, BOARD_SIZE};
+pub use outcome::{GameOutcome, OutcomeReason};
+pub use record::GameRecord;
+pub use rules::{NoMovesRule, Rules};
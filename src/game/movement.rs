@@ -1,28 +1,17 @@
-use super::{board::Move, Board, Coord};
-
-trait AbsDiff {
-    fn abs_dif(self, other: u8) -> u8;
-}
-
-impl AbsDiff for u8 {
-    fn abs_dif(self, other: u8) -> u8 {
-        if self >= other {
-            self - other
-        } else {
-            other - self
-        }
-    }
-}
+use super::{board::Move, Board, Coord, Species};
 
 pub fn valid_move(board: &Board, mv: &Move) -> bool {
     let from = &mv.from;
     let to = &mv.to;
     if board.wolf == *from {
-        from.x.abs_dif(to.x) == 1
-            && from.y.abs_dif(to.y) == 1
+        from.x().abs_diff(to.x()) == 1
+            && from.y().abs_diff(to.y()) == 1
             && board.sheeps.iter().all(|s| s != to)
     } else {
-        from.y.saturating_sub(to.y) == 1 && to.x.abs_dif(from.x) == 1 && board.wolf != *to
+        board.sheeps.contains(from)
+            && from.y().saturating_sub(to.y()) == 1
+            && to.x().abs_diff(from.x()) == 1
+            && board.iter().all(|p| p != to)
     }
 }
 
@@ -30,96 +19,54 @@ pub fn all_available_sheeps_moves(board: &Board) -> Vec<Move> {
     board
         .sheeps
         .iter()
-        .flat_map(|s| {
-            sheep_moves(s)
-                .into_iter()
-                .map(|x| Move::new(s.clone(), Coord::new(x.0, x.1)))
-        })
+        .flat_map(|s| sheep_moves(s).map(|to| Move::new(s.clone(), to)))
         .filter(|sheep_move| board.wolf != sheep_move.to)
         .filter(|sheep_move| !board.sheeps.contains(&sheep_move.to))
         .collect()
 }
 
 pub fn all_available_wolf_moves(wolf: &Coord, sheeps: &[Coord]) -> Vec<Move> {
-    wolf_moves(wolf)
-        .iter()
-        .map(|w| Coord::new(w.0, w.1))
+    wolf.diagonals()
         .filter(|w| sheeps.iter().all(|s| *s != *w))
         .map(|w| Move::new(wolf.clone(), w))
         .collect()
 }
 
-fn sheep_moves(coord: &Coord) -> Vec<(u8, u8)> {
-    let first = coord.x;
-    let second = coord.y;
-    match first {
-        0 => match second {
-            0 => vec![],
-            1..=6 => vec![(first + 1, second - 1)],
-            7 => vec![(first + 1, second - 1)],
-            _ => panic!("Coord out of bounds"),
-        },
-        1..=6 => match second {
-            0 => vec![],
-            1..=6 => vec![(first + 1, second - 1), (first - 1, second - 1)],
-            7 => vec![(first - 1, second - 1), (first + 1, second - 1)],
-            _ => panic!("Coord out of bounds"),
-        },
-        7 => match second {
-            0 => vec![],
-            1..=6 => vec![(first - 1, second - 1)],
-            7 => vec![(first - 1, second - 1)],
-            _ => panic!("Coord out of bounds"),
-        },
-        _ => panic!("Coord out of bounds"),
+pub fn all_available_moves(board: &Board) -> Vec<Move> {
+    match board.currently_moving {
+        Species::Wolf => all_available_wolf_moves(&board.wolf, &board.sheeps),
+        Species::Sheep => all_available_sheeps_moves(board),
     }
 }
 
-fn wolf_moves(coord: &Coord) -> Vec<(u8, u8)> {
-    let first = coord.x;
-    let second = coord.y;
-    match first {
-        0 => match second {
-            0 => vec![(first + 1, second + 1)],
-            1..=6 => vec![(first + 1, second + 1), (first + 1, second - 1)],
-            7 => vec![(first + 1, second - 1)],
-            _ => panic!("Coord out of bounds"),
-        },
-        1..=6 => match second {
-            0 => vec![(first - 1, second + 1), (first + 1, second + 1)],
-            1..=6 => vec![
-                (first + 1, second + 1),
-                (first + 1, second - 1),
-                (first - 1, second + 1),
-                (first - 1, second - 1),
-            ],
-            7 => vec![(first - 1, second - 1), (first + 1, second - 1)],
-            _ => panic!("Coord out of bounds"),
-        },
-        7 => match second {
-            0 => vec![(first - 1, second + 1)],
-            1..=6 => vec![(first - 1, second + 1), (first - 1, second - 1)],
-            7 => vec![(first - 1, second - 1)],
-            _ => panic!("Coord out of bounds"),
-        },
-        _ => panic!("Coord out of bounds"),
-    }
+/// Squares the piece on `from` may move to, empty if it isn't the side to move.
+pub fn legal_destinations(board: &Board, from: &Coord) -> Vec<Coord> {
+    all_available_moves(board)
+        .into_iter()
+        .filter(|mv| mv.from == *from)
+        .map(|mv| mv.to)
+        .collect()
+}
+
+fn sheep_moves(coord: &Coord) -> impl Iterator<Item = Coord> + '_ {
+    [(1, -1), (-1, -1)]
+        .into_iter()
+        .filter_map(|(dx, dy)| coord.offset(dx, dy))
 }
 
 pub fn wolf_cant_move(board: &Board) -> bool {
-    let available_moves = wolf_moves(&board.wolf);
-    let mut available_moves = available_moves
-        .iter()
-        .map(|x| Coord::new(x.0, x.1))
-        .filter(|m| board.sheeps.iter().all(|s| s != m));
-    available_moves.next().is_none()
+    all_available_wolf_moves(&board.wolf, &board.sheeps).is_empty()
 }
 
 pub fn move_pin(board: &mut Board, mv: &Move) -> bool {
     if valid_move(board, mv) {
-        let to_move = board.iter_mut().find(|p| *p == &mv.from).unwrap();
-        *to_move = mv.to.clone();
-        true
+        match board.iter_mut().find(|p| *p == &mv.from) {
+            Some(to_move) => {
+                *to_move = mv.to.clone();
+                true
+            }
+            None => false,
+        }
     } else {
         false
     }
@@ -131,7 +78,7 @@ mod tests {
     use crate::game::Board;
 
     fn xy(x: u8, y: u8) -> Coord {
-        Coord::new(x, y)
+        Coord::new(x, y).unwrap()
     }
 
     fn mv(x1: u8, y1: u8, x2: u8, y2: u8) -> Move {
@@ -145,44 +92,44 @@ mod tests {
     #[test]
     fn valid_move_sheep_should_move_only_up() {
         let mut board = Board::default();
-        assert!(valid_move(&board, &mv(1, 7, 0, 6)));
-        assert!(valid_move(&board, &mv(1, 7, 2, 6)));
+        assert!(valid_move(&board, &mv(2, 7, 1, 6)));
+        assert!(valid_move(&board, &mv(2, 7, 3, 6)));
 
-        board.sheeps[0] = xy(0, 6);
+        board.sheeps[0] = xy(1, 6);
 
-        assert!(valid_move(&board, &mv(0, 6, 1, 5)));
-        assert!(!valid_move(&board, &mv(0, 6, 1, 7)));
-        assert!(!valid_move(&board, &mv(0, 6, 2, 6)));
+        assert!(valid_move(&board, &mv(1, 6, 2, 5)));
+        assert!(!valid_move(&board, &mv(1, 6, 0, 7)));
+        assert!(!valid_move(&board, &mv(1, 6, 3, 6)));
     }
 
     #[test]
     fn valid_move_wolf_should_move_anywhere() {
         let mut board = Board::default();
-        board.wolf = xy(board.wolf.x, board.wolf.y + 2);
+        board.wolf = xy(board.wolf.x(), board.wolf.y() + 2);
         assert!(valid_move(
             &board,
-            &mv_c(&board.wolf, &xy(board.wolf.x + 1, board.wolf.y + 1))
+            &mv_c(&board.wolf, &xy(board.wolf.x() + 1, board.wolf.y() + 1))
         ));
         assert!(valid_move(
             &board,
-            &mv_c(&board.wolf, &xy(board.wolf.x + 1, board.wolf.y - 1))
+            &mv_c(&board.wolf, &xy(board.wolf.x() + 1, board.wolf.y() - 1))
         ));
         assert!(valid_move(
             &board,
-            &mv_c(&board.wolf, &xy(board.wolf.x - 1, board.wolf.y + 1))
+            &mv_c(&board.wolf, &xy(board.wolf.x() - 1, board.wolf.y() + 1))
         ));
         assert!(valid_move(
             &board,
-            &mv_c(&board.wolf, &xy(board.wolf.x - 1, board.wolf.y - 1))
+            &mv_c(&board.wolf, &xy(board.wolf.x() - 1, board.wolf.y() - 1))
         ));
 
         assert!(!valid_move(
             &board,
-            &mv_c(&board.wolf, &xy(board.wolf.x + 3, board.wolf.y))
+            &mv_c(&board.wolf, &xy(board.wolf.x() + 2, board.wolf.y()))
         ));
         assert!(!valid_move(
             &board,
-            &mv_c(&board.wolf, &xy(board.wolf.x + 3, board.wolf.y + 3))
+            &mv_c(&board.wolf, &xy(board.wolf.x() + 3, board.wolf.y() + 3))
         ));
     }
 
@@ -208,7 +155,7 @@ mod tests {
 
         let all_moves = all_available_sheeps_moves(&board);
 
-        let all_possible = vec![
+        let all_possible = [
             Move::new(board.sheeps[0].clone(), xy(1, 6)),
             Move::new(board.sheeps[1].clone(), xy(1, 6)),
             Move::new(board.sheeps[1].clone(), xy(3, 6)),
@@ -226,4 +173,20 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn legal_destinations_should_only_list_moves_of_the_side_to_move() {
+        let board = Board::default();
+
+        assert_eq!(legal_destinations(&board, &xy(3, 0)), [xy(4, 1), xy(2, 1)]);
+        assert!(legal_destinations(&board, &xy(0, 7)).is_empty());
+    }
+
+    #[test]
+    fn move_pin_should_ignore_moves_from_empty_squares() {
+        let mut board = Board::default();
+
+        assert!(!move_pin(&mut board, &mv(1, 6, 0, 5)));
+        assert_eq!(board.to_string(), Board::default().to_string());
+    }
 }
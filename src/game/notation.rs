@@ -0,0 +1,302 @@
+use std::{fmt, str::FromStr};
+
+use super::{engine, movement::all_available_moves, Board, Coord, GameRecord, Move, NoMovesRule, Rules, Species};
+
+/// Files run `a`..`h` left to right, ranks `1`..`8` from the sheep side up,
+/// so the wolf starts on `d8` and the sheep on `a1 c1 e1 g1`.
+const FILES: &[u8; 8] = b"abcdefgh";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotationError {
+    pub input: String,
+}
+
+impl NotationError {
+    fn new(input: &str) -> Self {
+        Self {
+            input: input.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid notation: '{}'", self.input)
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+impl fmt::Display for Coord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", FILES[self.x() as usize] as char, 8 - self.y())
+    }
+}
+
+impl FromStr for Coord {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.as_bytes() {
+            [file @ b'a'..=b'h', rank @ b'1'..=b'8'] => {
+                Coord::new(file - b'a', 8 - (rank - b'0')).map_err(|_| NotationError::new(s))
+            }
+            _ => Err(NotationError::new(s)),
+        }
+    }
+}
+
+impl From<Coord> for String {
+    fn from(coord: Coord) -> Self {
+        coord.to_string()
+    }
+}
+
+impl TryFrom<String> for Coord {
+    type Error = NotationError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.from, self.to)
+    }
+}
+
+impl FromStr for Move {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 4 || !s.is_ascii() {
+            return Err(NotationError::new(s));
+        }
+        let (from, to) = s.split_at(2);
+        match (from.parse(), to.parse()) {
+            (Ok(from), Ok(to)) => Ok(Move::new(from, to)),
+            _ => Err(NotationError::new(s)),
+        }
+    }
+}
+
+impl From<Move> for String {
+    fn from(mv: Move) -> Self {
+        mv.to_string()
+    }
+}
+
+impl TryFrom<String> for Move {
+    type Error = NotationError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for Species {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Species::Wolf => write!(f, "w"),
+            Species::Sheep => write!(f, "s"),
+        }
+    }
+}
+
+impl FromStr for Species {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "w" => Ok(Species::Wolf),
+            "s" => Ok(Species::Sheep),
+            _ => Err(NotationError::new(s)),
+        }
+    }
+}
+
+/// Position string: `<wolf> <sheep>,<sheep>,… <side to move>`,
+/// e.g. `d8 a1,c1,e1,g1 w` for the starting position.
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sheeps: Vec<_> = self.sheeps.iter().map(|s| s.to_string()).collect();
+        write!(f, "{} {} {}", self.wolf, sheeps.join(","), self.currently_moving)
+    }
+}
+
+impl FromStr for Board {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split_whitespace().collect();
+        let [wolf, sheeps, moving] = parts[..] else {
+            return Err(NotationError::new(s));
+        };
+        let sheeps = sheeps
+            .split(',')
+            .map(str::parse)
+            .collect::<Result<Vec<Coord>, _>>()?;
+        let board = Board {
+            wolf: wolf.parse()?,
+            sheeps,
+            selected: None,
+            currently_moving: moving.parse()?,
+        };
+        // no two pins can share a square
+        let pins: Vec<_> = std::iter::once(&board.wolf).chain(&board.sheeps).collect();
+        if (1..pins.len()).any(|index| pins[index..].contains(&pins[index - 1])) {
+            return Err(NotationError::new(s));
+        }
+        Ok(board)
+    }
+}
+
+impl From<Board> for String {
+    fn from(board: Board) -> Self {
+        board.to_string()
+    }
+}
+
+impl TryFrom<String> for Board {
+    type Error = NotationError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for NoMovesRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NoMovesRule::Pass => write!(f, "pass"),
+            NoMovesRule::Loss => write!(f, "loss"),
+            NoMovesRule::Draw => write!(f, "draw"),
+        }
+    }
+}
+
+/// Saved game text, one line each for the starting position, the rules for
+/// the wolf and the sheep without moves, and the moves:
+/// ```text
+/// position d8 a1,c1,e1,g1 w
+/// rules loss loss
+/// moves d8c7 a1b2
+/// ```
+/// The rules line may be left out for the default rules.
+impl fmt::Display for GameRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "position {}", self.start)?;
+        writeln!(f, "rules {} {}", self.rules.wolf_without_moves, self.rules.sheep_without_moves)?;
+        write!(f, "moves")?;
+        self.moves.iter().try_for_each(|mv| write!(f, " {mv}"))?;
+        writeln!(f)
+    }
+}
+
+impl FromStr for GameRecord {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut start = None;
+        let mut rules = Rules::default();
+        let mut moves = Vec::new();
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            let (key, rest) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+            match key {
+                "position" => start = Some(rest.parse::<Board>()?),
+                "rules" => {
+                    let [wolf, sheep] = rest.split_whitespace().collect::<Vec<_>>()[..] else {
+                        return Err(NotationError::new(line));
+                    };
+                    rules = Rules {
+                        wolf_without_moves: wolf.parse().map_err(|_: String| NotationError::new(wolf))?,
+                        sheep_without_moves: sheep.parse().map_err(|_: String| NotationError::new(sheep))?,
+                    };
+                }
+                "moves" => {
+                    moves = rest
+                        .split_whitespace()
+                        .map(str::parse)
+                        .collect::<Result<Vec<Move>, _>>()?
+                }
+                _ => return Err(NotationError::new(line)),
+            }
+        }
+        let start = start.ok_or_else(|| NotationError::new(s))?;
+        let mut board = start.clone();
+        let mut outcome = engine::game_outcome(&board, &rules);
+        for mv in &moves {
+            // nothing may be played once the game is decided
+            if outcome.is_some() || !all_available_moves(&board).contains(mv) {
+                return Err(NotationError::new(&mv.to_string()));
+            }
+            outcome = engine::handle_move(&mut board, mv, &rules);
+        }
+        Ok(GameRecord { start, rules, moves })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coord_notation_should_round_trip_every_dark_square() {
+        for x in 0..8 {
+            for y in 0..8 {
+                if let Ok(coord) = Coord::new(x, y) {
+                    assert_eq!(coord.to_string().parse::<Coord>(), Ok(coord));
+                }
+            }
+        }
+        assert_eq!(Board::default().wolf.to_string(), "d8");
+        assert!("a8".parse::<Coord>().is_err());
+        assert!("i1".parse::<Coord>().is_err());
+        assert!("a9".parse::<Coord>().is_err());
+    }
+
+    #[test]
+    fn board_notation_should_describe_starting_position() {
+        let board = Board::default();
+
+        assert_eq!(board.to_string(), "d8 a1,c1,e1,g1 w");
+
+        let parsed: Board = "d8 a1,c1,e1,g1 w".parse().unwrap();
+        assert_eq!(parsed.wolf, board.wolf);
+        assert_eq!(parsed.sheeps, board.sheeps);
+        assert_eq!("d8 a1,c1,e1 w".parse::<Board>().unwrap().sheeps.len(), 3);
+        assert!("d8 a1,,e1 w".parse::<Board>().is_err());
+        assert!("d8 a1,c1,e1,g1".parse::<Board>().is_err());
+        assert!("d8 d8,c1,e1,g1 w".parse::<Board>().is_err(), "wolf on a sheep");
+        assert!("d8 a1,a1,e1,g1 s".parse::<Board>().is_err(), "sheep on a sheep");
+    }
+
+    #[test]
+    fn game_record_notation_should_round_trip() {
+        let mut record = GameRecord::default();
+        record.rules.sheep_without_moves = NoMovesRule::Pass;
+        record.push("d8c7".parse().unwrap());
+        record.push("a1b2".parse().unwrap());
+
+        let text = record.to_string();
+
+        assert_eq!(text, "position d8 a1,c1,e1,g1 w\nrules loss pass\nmoves d8c7 a1b2\n");
+        let parsed: GameRecord = text.parse().unwrap();
+        assert_eq!(parsed.rules, record.rules);
+        assert_eq!(parsed.moves, record.moves);
+        assert!("position d8 a1,c1,e1,g1 w\nmoves d8c7 d8c7".parse::<GameRecord>().is_err());
+        assert!("moves d8c7".parse::<GameRecord>().is_err());
+        assert!(
+            "position d8 a1,c1,e1,g1 w\nmoves a1b2".parse::<GameRecord>().is_err(),
+            "sheep can't move on the wolf's turn"
+        );
+    }
+
+    #[test]
+    fn game_record_notation_should_reject_moves_after_the_game_ended() {
+        let won = "position e3 b2,d2,g3,h2 w\nmoves e3f2";
+        assert!(won.parse::<GameRecord>().is_ok());
+        assert!(format!("{won} b2c3").parse::<GameRecord>().is_err());
+    }
+}
@@ -0,0 +1,78 @@
+use std::fmt;
+
+use super::Species;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum OutcomeReason {
+    WolfBrokeThrough,
+    WolfTrapped,
+    SheepImmobilized,
+    Resignation,
+    Timeout,
+    /// Neither side has a move left to make.
+    Stalemate,
+}
+
+/// How a game ended. `winner` is `None` for a draw.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameOutcome {
+    pub winner: Option<Species>,
+    pub reason: OutcomeReason,
+}
+
+impl GameOutcome {
+    pub fn win(winner: Species, reason: OutcomeReason) -> Self {
+        Self {
+            winner: Some(winner),
+            reason,
+        }
+    }
+
+    pub fn draw(reason: OutcomeReason) -> Self {
+        Self {
+            winner: None,
+            reason,
+        }
+    }
+
+    pub fn resignation(resigning: &Species) -> Self {
+        Self::win(resigning.opposite(), OutcomeReason::Resignation)
+    }
+
+    pub fn timeout(flagged: &Species) -> Self {
+        Self::win(flagged.opposite(), OutcomeReason::Timeout)
+    }
+
+    pub fn is_win_for(&self, species: &Species) -> bool {
+        self.winner.as_ref() == Some(species)
+    }
+}
+
+impl fmt::Display for OutcomeReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            OutcomeReason::WolfBrokeThrough => "wolf broke through",
+            OutcomeReason::WolfTrapped => "wolf trapped",
+            OutcomeReason::SheepImmobilized => "sheep immobilized",
+            OutcomeReason::Resignation => "resignation",
+            OutcomeReason::Timeout => "timeout",
+            OutcomeReason::Stalemate => "stalemate",
+        };
+        write!(f, "{text}")
+    }
+}
+
+impl fmt::Display for GameOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.winner {
+            Some(winner) => write!(f, "{winner:?} won by {}", self.reason),
+            None => write!(f, "Draw by {}", self.reason),
+        }
+    }
+}
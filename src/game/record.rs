@@ -0,0 +1,114 @@
+use super::{engine, Board, Move, Rules, Species};
+
+/// A game as its starting position plus every move played from it.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameRecord {
+    pub start: Board,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rules: Rules,
+    pub moves: Vec<Move>,
+}
+
+impl GameRecord {
+    pub fn new(start: Board) -> Self {
+        Self {
+            start,
+            rules: Rules::default(),
+            moves: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, mv: Move) {
+        self.moves.push(mv);
+    }
+
+    /// Position after the first `ply` moves were played.
+    pub fn position_at(&self, ply: usize) -> Board {
+        let mut board = self.start.clone();
+        for mv in self.moves.iter().take(ply) {
+            let _ = engine::handle_move(&mut board, mv, &self.rules);
+        }
+        board
+    }
+
+    /// Every move with the side that made it and its move number, which
+    /// goes up with each wolf move like a chess move number with white.
+    pub fn numbered_moves(&self) -> Vec<(usize, Species, &Move)> {
+        let mut board = self.start.clone();
+        let mut number = 0;
+        self.moves
+            .iter()
+            .map(|mv| {
+                let mover = board.currently_moving.clone();
+                if mover == Species::Wolf {
+                    number += 1;
+                }
+                let _ = engine::handle_move(&mut board, mv, &self.rules);
+                (number.max(1), mover, mv)
+            })
+            .collect()
+    }
+
+    pub fn current(&self) -> Board {
+        self.position_at(self.moves.len())
+    }
+}
+
+impl Default for GameRecord {
+    fn default() -> Self {
+        Self::new(Board::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(moves: &[&str]) -> GameRecord {
+        let mut record = GameRecord::default();
+        for mv in moves {
+            record.push(mv.parse().unwrap());
+        }
+        record
+    }
+
+    #[test]
+    fn position_at_should_replay_moves_from_start() {
+        let record = record(&["d8c7", "a1b2", "c7d6"]);
+
+        assert_eq!(record.position_at(0).to_string(), "d8 a1,c1,e1,g1 w");
+        assert_eq!(record.position_at(2).to_string(), "c7 b2,c1,e1,g1 w");
+        assert_eq!(record.current().to_string(), "d6 b2,c1,e1,g1 s");
+    }
+
+    #[test]
+    fn numbered_moves_should_count_wolf_moves() {
+        let record = record(&["d8c7", "a1b2", "c7d6"]);
+
+        let numbered: Vec<_> = record
+            .numbered_moves()
+            .into_iter()
+            .map(|(number, species, mv)| format!("{number} {species} {mv}"))
+            .collect();
+
+        assert_eq!(numbered, ["1 w d8c7", "1 s a1b2", "2 w c7d6"]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_should_use_move_notation() {
+        let record = record(&["d8c7", "a1b2"]);
+
+        let json = serde_json::to_string(&record).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"start":"d8 a1,c1,e1,g1 w","rules":{"wolf_without_moves":"loss","sheep_without_moves":"loss"},"moves":["d8c7","a1b2"]}"#
+        );
+        let parsed: GameRecord = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.current().to_string(), record.current().to_string());
+        assert!(serde_json::from_str::<GameRecord>(&json.replace("a1b2", "a1z9")).is_err());
+        assert!(serde_json::from_str::<GameRecord>(&json.replace("d8 a1", "a1 a1")).is_err());
+    }
+}
@@ -0,0 +1,79 @@
+use std::str::FromStr;
+
+use super::Species;
+
+/// What happens when the side to move has no legal move.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum NoMovesRule {
+    /// The turn goes to the opponent.
+    Pass,
+    /// The stuck side loses.
+    Loss,
+    /// The game ends in a draw.
+    Draw,
+}
+
+/// Rule set a game is played under. A position where neither side can move
+/// is a draw regardless of these.
+///
+/// The GUI always plays the default rules. Engines are told others with
+/// `setoption name rules value <wolf>,<sheep>`, e.g. `loss,pass`, and library
+/// users set them on the [`super::GameRecord`] a game is played in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rules {
+    pub wolf_without_moves: NoMovesRule,
+    pub sheep_without_moves: NoMovesRule,
+}
+
+impl Rules {
+    pub fn without_moves(&self, species: &Species) -> &NoMovesRule {
+        match species {
+            Species::Wolf => &self.wolf_without_moves,
+            Species::Sheep => &self.sheep_without_moves,
+        }
+    }
+}
+
+impl FromStr for NoMovesRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pass" => Ok(NoMovesRule::Pass),
+            "loss" => Ok(NoMovesRule::Loss),
+            "draw" => Ok(NoMovesRule::Draw),
+            _ => Err(format!("unknown rule '{s}'")),
+        }
+    }
+}
+
+/// Parses the wolf's rule and the sheep's, separated by a comma.
+impl FromStr for Rules {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((wolf, sheep)) = s.split_once(',') else {
+            return Err(format!("rules '{s}' should be the wolf's and the sheep's, like 'loss,pass'"));
+        };
+        Ok(Self {
+            wolf_without_moves: wolf.parse()?,
+            sheep_without_moves: sheep.parse()?,
+        })
+    }
+}
+
+/// A trapped wolf loses and so do sheep that cannot move anymore.
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            wolf_without_moves: NoMovesRule::Loss,
+            sheep_without_moves: NoMovesRule::Loss,
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! Wolf and sheep game core, free of any GUI dependency.
+//!
+//! - [`game`] holds the board model, move generation and the rules engine.
+//! - [`session`] holds who controls each side and plays the computer's turns.
+//! - [`ai`] holds the [`ai::AI`] trait and the bundled computer players.
+//! - [`protocol`] is the text protocol used to talk to external engines.
+
+pub mod ai;
+pub mod game;
+pub mod protocol;
+pub mod session;
@@ -1,21 +1,70 @@
-mod ai;
+mod animation;
+mod choices;
 mod drawing;
-mod game;
 mod mode;
+mod pace;
+mod sprite;
+mod theme;
 
-use std::{time::Duration, ops::DerefMut};
+use std::time::{Duration, Instant};
 
-use ai::{get_ai, AITypes, learning::{LearningProgress, learning_session}};
-use game::{engine, Board, Controls, Coord, Move, Species};
+use wolf_and_sheep::{ai, game, session};
+
+use animation::{Animation, FRAME};
+use ai::{AITypes, Budget, ComputerPlayer, RemembranceAI, learning::{LearningProgress, learning_session}};
+use choices::{ClockChoice, ControlChoice, SideChoices};
+use game::{editor::{self, check_position, EditTool}, engine, movement::legal_destinations, Board, Clocks, Coord, GameOutcome, GameRecord, Move, Species};
+use session::{Control, Controls, PendingMoves, Thought};
 use iced::{Application, Command, Settings};
 use mode::GameMode;
+use pace::{Pace, TICK};
+use theme::{Themes, THEMES_FILE};
+
+/// How long a refused move stays marked on the board.
+const ILLEGAL_CLICK_SHOWN: Duration = Duration::from_millis(600);
+/// File games are saved to and loaded from, in the working directory.
+const SAVED_GAME: &str = "wolf-and-sheep-game.txt";
 
 struct App {
     board: Board,
+    record: GameRecord,
     controls: Controls,
+    /// Computer moves being worked out in the background.
+    pending: PendingMoves,
     mode: GameMode,
     learning_progress: LearningProgress,
-    ai: Box<dyn ai::AI>,
+    choices: SideChoices,
+    /// Remembrance AIs trained in learning mode, selectable as the sheep.
+    trained: Vec<RemembranceAI>,
+    /// Last thing worth telling the player, like an external AI failing
+    /// or the result of a save or load.
+    notice: Option<String>,
+    pace: Pace,
+    time_control: ClockChoice,
+    /// Clocks of the current game, if it is timed.
+    clocks: Option<Clocks>,
+    /// When the clocks were last charged.
+    clock_read: Instant,
+    last_outcome: Option<GameOutcome>,
+    /// Whether the board marks the wolf's next squares.
+    show_threats: bool,
+    /// Earlier position of the current game looked at from the move list.
+    viewing: Option<usize>,
+    /// Last move sliding into place.
+    animation: Option<Animation>,
+    /// Square clicked for an illegal move, and when.
+    illegal_click: Option<(Coord, Instant)>,
+    /// Saved game being replayed instead of the last one played.
+    replayed: Option<GameRecord>,
+    /// Pace of stepping through a replay on its own, paused unless auto-playing.
+    autoplay: Pace,
+    /// Position set up in the board editor.
+    edited: Board,
+    edit_tool: EditTool,
+    themes: Themes,
+    /// Whether the board is turned around from the side it faces by default.
+    flipped: bool,
+    widgets: drawing::Widgets,
 }
 
 #[derive(Debug)]
@@ -23,24 +72,248 @@ enum Msg {
     PinSelected(Coord),
     PinMoved(Coord),
     Tick,
-    ControlChanged(Species),
+    Frame,
+    AIThought(Result<Thought, String>),
+    ControlChanged(Species, ControlChoice),
+    BudgetChanged(Species, Budget),
+    TimeControlChanged(ClockChoice),
     NewMode(GameMode),
-    AILearned(Box<dyn ai::AI + Send>),
+    AILearned(RemembranceAI),
+    Resign,
+    Rematch,
+    SwapSides,
+    AIDelayChanged(u32),
+    AnimationChanged(u32),
+    ThreatsToggled(bool),
+    ShowPly(usize),
+    ToggleAutoplay,
+    AutoplaySpeedChanged(u32),
+    LeaveReplay,
+    SaveGame,
+    LoadGame,
+    EditSquare(Coord),
+    EditToolChanged(EditTool),
+    EditorSideToggled,
+    EditorCleared,
+    EditorReset,
+    StartFromEdited { analyse: bool },
+    ThemeSelected(String),
+    FlipToggled(bool),
+    TogglePause,
+    Step,
+}
+
+impl App {
+    fn finish_game(&mut self, outcome: GameOutcome) {
+        self.pending.game_ended(&outcome);
+        session::handle_game_end(&mut self.controls, &outcome);
+        self.last_outcome = Some(outcome);
+        self.mode = GameMode::GameOver;
+    }
+
+    /// Charges the side to move for the time since the clocks were last read.
+    /// Clocks stand still outside of a game, and while the computer to move is
+    /// paused. Pausing doesn't hold up players, so their clocks keep running.
+    fn read_clock(&mut self) -> Option<GameOutcome> {
+        let now = Instant::now();
+        let elapsed = now - std::mem::replace(&mut self.clock_read, now);
+        if (self.pace.paused && self.computer_to_move()) || !matches!(self.mode, GameMode::Playing) {
+            return None;
+        }
+        self.clocks.as_mut()?.charge(&self.board.currently_moving, elapsed)
+    }
+
+    /// Plays a move through `play` on the clock, giving the mover its increment.
+    /// `play` still runs after a flag fall, which stops any computer move.
+    /// Returns whether a move was made.
+    fn clocked_move(&mut self, play: impl FnOnce(&mut Self) -> Option<GameOutcome>) -> bool {
+        if let Some(timeout) = self.read_clock() {
+            self.finish_game(timeout);
+        }
+        let mover = self.board.currently_moving.clone();
+        let moves_before = self.record.moves.len();
+        let outcome = play(self);
+        let moved = self.record.moves.len() > moves_before;
+        if moved {
+            if let Some(clocks) = &mut self.clocks {
+                clocks.moved(&mover);
+            }
+        }
+        if let Some(outcome) = outcome {
+            self.finish_game(outcome);
+        }
+        moved
+    }
+
+    fn animate(&mut self, mv: Option<Move>) {
+        self.animation = mv.map(|mv| Animation::new(mv, self.pace.animation()));
+    }
+
+    fn replay_record(&self) -> &GameRecord {
+        self.replayed.as_ref().unwrap_or(&self.record)
+    }
+
+    /// Slides the pin moved between two neighbouring replay plies, backwards when stepping back.
+    fn animate_replay_step(&mut self, from_ply: usize, to_ply: usize) {
+        let moves = &self.replay_record().moves;
+        let mv = match to_ply.checked_sub(from_ply) {
+            Some(1) => moves.get(from_ply).cloned(),
+            None if from_ply - to_ply == 1 => {
+                moves.get(to_ply).map(|mv| Move::new(mv.to.clone(), mv.from.clone()))
+            }
+            _ => None,
+        };
+        let duration = match self.autoplay.paused {
+            true => self.pace.animation(),
+            false => self.pace.animation().min(self.autoplay.interval()),
+        };
+        self.animation = mv.map(|mv| Animation::new(mv, duration));
+    }
+
+    fn save_game(&mut self) {
+        self.notice = Some(match std::fs::write(SAVED_GAME, self.record.to_string()) {
+            Ok(()) => format!("Saved to {SAVED_GAME}"),
+            Err(e) => format!("Couldn't save {SAVED_GAME}: {e}"),
+        });
+    }
+
+    fn load_game(&mut self) {
+        let loaded = std::fs::read_to_string(SAVED_GAME)
+            .map_err(|e| e.to_string())
+            .and_then(|text| text.parse::<GameRecord>().map_err(|e| e.to_string()));
+        match loaded {
+            Ok(record) => {
+                self.replayed = Some(record);
+                self.notice = None;
+                self.mode = GameMode::Replay { ply: 0 };
+            }
+            Err(e) => self.notice = Some(format!("Couldn't load {SAVED_GAME}: {e}")),
+        }
+    }
+
+    fn play_computer_turn(&mut self) -> Command<Msg> {
+        if !matches!(self.mode, GameMode::Playing) {
+            return Command::none();
+        }
+        let budget = self.choices.budget(&self.board.currently_moving);
+        match self.pending.start(&self.board, &mut self.controls, budget) {
+            Some(thinking) => Command::perform(
+                async move {
+                    tokio::task::spawn_blocking(move || thinking.think())
+                        .await
+                        .map_err(|error| format!("AI stopped thinking: {}", error))
+                },
+                Msg::AIThought,
+            ),
+            None => Command::none(),
+        }
+    }
+
+    fn computer_to_move(&self) -> bool {
+        matches!(self.controls.of(&self.board.currently_moving), Control::Computer(_))
+    }
+
+    /// The board faces the player's side: the sheep's at the bottom unless
+    /// they only play the wolf, then turned around if they flipped it.
+    fn wolf_at_bottom(&self) -> bool {
+        let wolf_only = matches!(self.controls.of(&Species::Wolf), Control::Player)
+            && matches!(self.controls.of(&Species::Sheep), Control::Computer(_));
+        wolf_only != self.flipped
+    }
+
+    /// Tells the player why a theme's board image is missing from a flipped board.
+    fn flipped_board_image(&self) -> Option<&'static str> {
+        (self.wolf_at_bottom() && self.themes.current().sprites.board.is_some())
+            .then_some("The theme's board image can't be flipped, so plain squares are shown")
+    }
+
+    fn set_control(&mut self, species: Species, choice: ControlChoice) {
+        self.pending.control_changed(&species);
+        let control = self.control_for(&choice);
+        session::handle_control_change(&mut self.controls, species.clone(), control);
+        *self.choices.of_mut(&species) = choice;
+    }
+
+    fn control_for(&self, choice: &ControlChoice) -> Control {
+        match choice {
+            ControlChoice::Player => Control::Player,
+            ControlChoice::Computer(ai_type) => Control::Computer(ComputerPlayer::new(ai_type.clone(), &self.record.rules)),
+            ControlChoice::Trained(index) => Control::Computer(ComputerPlayer {
+                kind: AITypes::Remembrance,
+                ai: Box::new(self.trained[*index].clone()),
+            }),
+        }
+    }
+
+    /// Plays the last game's starting position again.
+    fn new_game(&mut self) {
+        self.new_game_from(self.record.start.clone());
+    }
+
+    /// Starts a game from the edited position if it is playable. Analysing
+    /// hands both sides to the player, without a clock.
+    fn start_from_edited(&mut self, analyse: bool) {
+        if check_position(&self.edited, &self.record.rules).is_err() {
+            return;
+        }
+        if analyse {
+            self.set_control(Species::Wolf, ControlChoice::Player);
+            self.set_control(Species::Sheep, ControlChoice::Player);
+            self.time_control = ClockChoice(None);
+        }
+        self.new_game_from(self.edited.clone());
+    }
+
+    fn new_game_from(&mut self, start: Board) {
+        self.pending.stop_all();
+        engine::handle_new_game(&mut self.board, &mut self.record, start);
+        self.clocks = self.time_control.0.map(Clocks::new);
+        self.clock_read = Instant::now();
+        self.animation = None;
+        self.viewing = None;
+        self.last_outcome = None;
+        self.mode = GameMode::Playing;
+    }
 }
 
 impl iced::Application for App {
     type Executor = iced::executor::Default;
     type Message = Msg;
-    type Flags = ();
+    type Flags = Option<AITypes>;
 
-    fn new(_flags: Self::Flags) -> (Self, iced::Command<Self::Message>) {
+    fn new(ai_type: Self::Flags) -> (Self, iced::Command<Self::Message>) {
+        let mut themes = Themes::default();
+        // without a themes file there are just the built-in ones
+        let notice = std::fs::read_to_string(THEMES_FILE).ok().and_then(|text| {
+            themes.load(&text).err().map(|e| format!("Couldn't load {THEMES_FILE}: {e}"))
+        });
         (
             Self {
                 mode: GameMode::new(),
                 board: Default::default(),
-                ai: get_ai(AITypes::Smart),
+                record: Default::default(),
+                choices: SideChoices::new(ai_type),
+                trained: Vec::new(),
+                notice,
+                pace: Default::default(),
+                time_control: ClockChoice::default(),
+                clocks: None,
+                clock_read: Instant::now(),
                 controls: Default::default(),
+                pending: Default::default(),
+                last_outcome: None,
+                illegal_click: None,
+                animation: None,
+                show_threats: false,
+                viewing: None,
+                replayed: None,
+                autoplay: Pace::paused(),
+                edited: Board::default(),
+                edit_tool: EditTool::default(),
+                themes,
+                flipped: false,
                 learning_progress: LearningProgress::new(),
+                widgets: Default::default(),
             },
             Command::none(),
         )
@@ -52,67 +325,245 @@ impl iced::Application for App {
 
     fn update(&mut self, message: Self::Message) -> iced::Command<Self::Message> {
         match message {
+            // the computer's pins are its own, even when it is slow to move them
+            Msg::PinSelected(_) | Msg::PinMoved(_) if self.computer_to_move() => {}
             Msg::PinSelected(selected) => {
                 self.board.selected = Some(selected);
             }
             Msg::PinMoved(moved_to) => {
-                self.board.selected.clone().map(|selected| {
-                    engine::handle_move(&mut self.board, &Move::new(selected, moved_to))
-                        .map(|winner| {
-                            self.ai.feedback(false);
-                            engine::handle_win(winner, &mut self.board)
-                    })
-                });
+                if let Some(selected) = self.board.selected.clone() {
+                    if !legal_destinations(&self.board, &selected).contains(&moved_to) {
+                        self.illegal_click = Some((moved_to, Instant::now()));
+                        return Command::none();
+                    }
+                    let mv = Move::new(selected, moved_to);
+                    self.clocked_move(|app| match app.mode {
+                        GameMode::Playing => {
+                            engine::handle_recorded_move(&mut app.board, &mut app.record, &mv)
+                        }
+                        _ => None,
+                    });
+                }
             }
             Msg::Tick => {
-                engine::handle_tick(&mut self.board, &self.controls, &mut self.ai)
-                    .map(|winner| engine::handle_win(winner, &mut self.board));
+                if self.illegal_click.as_ref().is_some_and(|(_, at)| at.elapsed() > ILLEGAL_CLICK_SHOWN) {
+                    self.illegal_click = None;
+                }
+                if let Some(timeout) = self.read_clock() {
+                    self.finish_game(timeout);
+                }
+                if let GameMode::Replay { ply } = self.mode {
+                    if self.autoplay.turn_due(Instant::now()) {
+                        if ply < self.replay_record().moves.len() {
+                            return self.update(Msg::NewMode(GameMode::Replay { ply: ply + 1 }));
+                        }
+                        self.autoplay.paused = true;
+                    }
+                }
+                if self.pace.turn_due(Instant::now()) {
+                    return self.play_computer_turn();
+                }
+            }
+            Msg::Frame => {
+                if self.animation.as_ref().is_some_and(|a| a.is_done(Instant::now())) {
+                    self.animation = None;
+                }
+            }
+            Msg::Step => return self.play_computer_turn(),
+            Msg::AIThought(Ok(mut thought)) => {
+                let crashed = thought.take_error();
+                let moved = self.clocked_move(|app| {
+                    app.pending.finish(thought, &mut app.board, &mut app.record, &mut app.controls)
+                });
+                if let Some(error) = crashed.or_else(|| self.controls.take_error()) {
+                    self.notice = Some(error);
+                }
+                // players see their own moves happen, computer moves slide in
+                if moved {
+                    self.animate(self.record.moves.last().cloned());
+                }
+            }
+            // thinking panics are caught, so the task only fails when it gets cancelled
+            // and the AI is gone with it
+            Msg::AIThought(Err(error)) => {
+                self.notice = Some(error);
+                if matches!(self.mode, GameMode::Playing) {
+                    self.finish_game(GameOutcome::resignation(&self.board.currently_moving));
+                }
+            }
+            Msg::AIDelayChanged(delay_ms) => self.pace.delay_ms = delay_ms,
+            Msg::AnimationChanged(animation_ms) => self.pace.animation_ms = animation_ms,
+            Msg::ThreatsToggled(show_threats) => self.show_threats = show_threats,
+            Msg::ShowPly(ply) => self.viewing = (ply < self.record.moves.len()).then_some(ply),
+            Msg::ToggleAutoplay => {
+                self.autoplay.paused = !self.autoplay.paused;
+                if let GameMode::Replay { ply } = self.mode {
+                    // playing from the end starts over
+                    if !self.autoplay.paused && ply == self.replay_record().moves.len() {
+                        self.mode = GameMode::Replay { ply: 0 };
+                    }
+                }
+            }
+            Msg::AutoplaySpeedChanged(delay_ms) => self.autoplay.delay_ms = delay_ms,
+            Msg::LeaveReplay => {
+                self.autoplay.paused = true;
+                self.animation = None;
+                self.mode = match self.replayed.take() {
+                    Some(_) => GameMode::ChoosingMode,
+                    None => GameMode::GameOver,
+                };
             }
-            Msg::ControlChanged(species) => {
-                engine::handle_control_change(&mut self.controls, species);
+            Msg::SaveGame => self.save_game(),
+            Msg::LoadGame => self.load_game(),
+            Msg::EditSquare(coord) => editor::edit_square(&mut self.edited, coord, self.edit_tool),
+            Msg::EditToolChanged(tool) => self.edit_tool = tool,
+            Msg::EditorSideToggled => {
+                self.edited.currently_moving = self.edited.currently_moving.opposite()
+            }
+            Msg::EditorCleared => self.edited.sheeps.clear(),
+            Msg::EditorReset => self.edited = Board::default(),
+            Msg::StartFromEdited { analyse } => self.start_from_edited(analyse),
+            Msg::ThemeSelected(name) => self.themes.select(&name),
+            Msg::FlipToggled(flipped) => self.flipped = flipped,
+            Msg::TogglePause => self.pace.paused = !self.pace.paused,
+            Msg::ControlChanged(species, choice) => self.set_control(species, choice),
+            Msg::BudgetChanged(species, budget) => *self.choices.budget_mut(&species) = budget,
+            Msg::TimeControlChanged(time_control) => {
+                self.time_control = time_control;
+                if self.record.moves.is_empty() {
+                    self.clocks = time_control.0.map(Clocks::new);
+                }
             }
             Msg::NewMode(mode) => {
+                self.notice = None;
+                if matches!(mode, GameMode::Playing) && self.last_outcome.is_some() {
+                    self.new_game_from(Board::default());
+                    return Command::none();
+                }
+                match (&self.mode, &mode) {
+                    (GameMode::Replay { ply: from }, GameMode::Replay { ply: to }) => {
+                        self.animate_replay_step(*from, *to)
+                    }
+                    _ => self.animation = None,
+                }
                 self.mode = mode;
                 if let GameMode::Learning = &self.mode {
                     self.learning_progress = LearningProgress::new();
                     return Command::perform(std::future::ready(
-                        ai::get_ai(AITypes::Remembrance)), Msg::AILearned)
+                        RemembranceAI::new()), Msg::AILearned)
                 }
             },
             Msg::AILearned(mut ai) => {
                 while !self.learning_progress.tick() {
-                    learning_session(ai.deref_mut(), Species::Sheep);
+                    learning_session(&mut ai, Species::Sheep);
                 }
-                self.ai = ai;
+                self.trained.push(ai);
+                self.choices.trained = self.trained.len();
+                self.set_control(Species::Sheep, ControlChoice::Trained(self.trained.len() - 1));
                 self.mode = GameMode::Playing;
             },
+            Msg::Resign => {
+                // the computer never resigns through the button
+                if let Some(species) = self.controls.player_side(&self.board.currently_moving) {
+                    self.finish_game(GameOutcome::resignation(&species));
+                }
+            }
+            Msg::Rematch => self.new_game(),
+            Msg::SwapSides => {
+                session::handle_swap_sides(&mut self.controls);
+                self.pending.sides_swapped();
+                self.choices.swap();
+                for species in [Species::Wolf, Species::Sheep] {
+                    if !self.choices.of(&species).can_play(&species) {
+                        let choice = ControlChoice::Computer(AITypes::default_for(&species));
+                        self.set_control(species, choice);
+                    }
+                }
+                self.new_game();
+            }
         }
         Command::none()
     }
 
     fn view(&mut self) -> iced::Element<'_, Self::Message> {
-        drawing::view(&self.board, &self.controls, &self.mode, &self.learning_progress).map(|m| match m {
+        let scene = drawing::Scene {
+            board: &self.board,
+            record: &self.record,
+            choices: &self.choices,
+            pace: &self.pace,
+            clocks: self.clocks.as_ref(),
+            time_control: self.time_control,
+            last_outcome: self.last_outcome.as_ref(),
+            illegal_click: self.illegal_click.as_ref().map(|(coord, _)| coord),
+            animation: self
+                .animation
+                .as_ref()
+                .map(|a| (a.mv.clone(), a.progress(Instant::now()))),
+            show_threats: self.show_threats,
+            viewing: self.viewing,
+            replayed: self.replayed.as_ref(),
+            autoplay: &self.autoplay,
+            edited: &self.edited,
+            edit_tool: self.edit_tool,
+            themes: &self.themes,
+            flipped: self.flipped,
+            wolf_at_bottom: self.wolf_at_bottom(),
+            mode: &self.mode,
+            progress: &self.learning_progress,
+            notice: self.notice.as_deref().or_else(|| self.flipped_board_image()),
+        };
+        drawing::view(&mut self.widgets, scene).map(|m| match m {
             drawing::GraphicMsg::PinSelected(selected) => Msg::PinSelected(selected),
             drawing::GraphicMsg::PinMoved(to) => Msg::PinMoved(to),
-            drawing::GraphicMsg::ControlChanged(species) => Msg::ControlChanged(species),
+            drawing::GraphicMsg::ControlChanged(species, choice) => Msg::ControlChanged(species, choice),
+            drawing::GraphicMsg::BudgetChanged(species, budget) => Msg::BudgetChanged(species, budget),
+            drawing::GraphicMsg::TimeControlChanged(time_control) => Msg::TimeControlChanged(time_control),
             drawing::GraphicMsg::ModeSelected(mode) => Msg::NewMode(mode),
+            drawing::GraphicMsg::Resign => Msg::Resign,
+            drawing::GraphicMsg::Rematch => Msg::Rematch,
+            drawing::GraphicMsg::SwapSides => Msg::SwapSides,
+            drawing::GraphicMsg::AIDelayChanged(delay_ms) => Msg::AIDelayChanged(delay_ms),
+            drawing::GraphicMsg::AnimationChanged(animation_ms) => Msg::AnimationChanged(animation_ms),
+            drawing::GraphicMsg::ThreatsToggled(show_threats) => Msg::ThreatsToggled(show_threats),
+            drawing::GraphicMsg::ShowPly(ply) => Msg::ShowPly(ply),
+            drawing::GraphicMsg::ToggleAutoplay => Msg::ToggleAutoplay,
+            drawing::GraphicMsg::AutoplaySpeedChanged(delay_ms) => Msg::AutoplaySpeedChanged(delay_ms),
+            drawing::GraphicMsg::LeaveReplay => Msg::LeaveReplay,
+            drawing::GraphicMsg::SaveGame => Msg::SaveGame,
+            drawing::GraphicMsg::LoadGame => Msg::LoadGame,
+            drawing::GraphicMsg::EditSquare(coord) => Msg::EditSquare(coord),
+            drawing::GraphicMsg::EditToolChanged(tool) => Msg::EditToolChanged(tool),
+            drawing::GraphicMsg::EditorSideToggled => Msg::EditorSideToggled,
+            drawing::GraphicMsg::EditorCleared => Msg::EditorCleared,
+            drawing::GraphicMsg::EditorReset => Msg::EditorReset,
+            drawing::GraphicMsg::StartFromEdited { analyse } => Msg::StartFromEdited { analyse },
+            drawing::GraphicMsg::ThemeSelected(name) => Msg::ThemeSelected(name),
+            drawing::GraphicMsg::FlipToggled(flipped) => Msg::FlipToggled(flipped),
+            drawing::GraphicMsg::TogglePause => Msg::TogglePause,
+            drawing::GraphicMsg::Step => Msg::Step,
         })
     }
 
     fn subscription(&self) -> iced::Subscription<Self::Message> {
-        iced::time::every(Duration::from_millis(100)).map(|_| Msg::Tick)
+        let ticks = iced::time::every(TICK).map(|_| Msg::Tick);
+        match self.animation {
+            Some(_) => iced::Subscription::batch([ticks, iced::time::every(FRAME).map(|_| Msg::Frame)]),
+            None => ticks,
+        }
     }
 }
 
 #[tokio::main]
-async fn main() {
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // e.g. `wolf-and-sheep random` or `wolf-and-sheep "external:python3 bot.py"`
+    let ai_type = std::env::args().nth(1).map(|name| name.parse()).transpose()?;
     App::run(Settings {
         window: iced::window::Settings {
-            resizable: false,
-            size: (500, 600),
+            size: (620, 760),
+            min_size: Some((480, 590)),
             ..Default::default()
         },
-        ..Default::default()
-    })
-    .unwrap()
+        ..Settings::with_flags(ai_type)
+    })?;
+    Ok(())
 }
@@ -1,8 +1,13 @@
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum GameMode {
     ChoosingMode,
     Playing,
     Learning,
+    GameOver,
+    /// Stepping through the finished game, showing the position after `ply` moves.
+    Replay { ply: usize },
+    /// Setting up a custom starting position.
+    Editing,
 }
 
 impl GameMode {
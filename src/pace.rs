@@ -0,0 +1,55 @@
+use std::time::{Duration, Instant};
+
+/// How often the app ticks, driving clocks and computer turns.
+pub const TICK: Duration = Duration::from_millis(50);
+
+/// How quickly computer-controlled turns, or the moves of a replay, are played.
+#[derive(Debug, Clone)]
+pub struct Pace {
+    /// Delay between two computer moves.
+    pub delay_ms: u32,
+    /// Computer turns only advance through single steps while paused.
+    pub paused: bool,
+    /// How long a move takes to slide across the board.
+    pub animation_ms: u32,
+    last_turn: Option<Instant>,
+}
+
+impl Pace {
+    /// Default pace, waiting to be resumed.
+    pub fn paused() -> Self {
+        Self {
+            paused: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn interval(&self) -> Duration {
+        Duration::from_millis(self.delay_ms.into())
+    }
+
+    /// Animations never outlast the delay, so fast games stay in sync.
+    pub fn animation(&self) -> Duration {
+        Duration::from_millis(self.animation_ms.min(self.delay_ms).into())
+    }
+
+    /// Whether the next computer turn may start at `now`, counting it as started if so.
+    pub fn turn_due(&mut self, now: Instant) -> bool {
+        if self.paused || self.last_turn.is_some_and(|last| now - last < self.interval()) {
+            return false;
+        }
+        self.last_turn = Some(now);
+        true
+    }
+}
+
+impl Default for Pace {
+    fn default() -> Self {
+        Self {
+            delay_ms: 500,
+            paused: false,
+            animation_ms: 250,
+            last_turn: None,
+        }
+    }
+}
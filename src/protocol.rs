@@ -0,0 +1,376 @@
+//! Line based engine protocol over stdin/stdout, modelled after UCI.
+//!
+//! A session looks like:
+//! ```text
+//! > wsp
+//! < id name wolf-and-sheep smart
+//! < wspok
+//! > newgame
+//! > position startpos
+//! > moves d8c7 a1b2
+//! > go movetime 500
+//! < bestmove c7d6
+//! > quit
+//! ```
+//! Positions use the notation from [`crate::game::notation`].
+//!
+//! Options are `ai`, taking an AI name like the engine binary's argument, and
+//! `rules`, taking a [`Rules`] like `loss,pass`.
+
+use std::{
+    fmt,
+    io::{self, BufRead, Write},
+    str::FromStr,
+    sync::atomic::AtomicBool,
+    time::Duration,
+};
+
+use crate::{
+    ai::{get_ai, AITypes, Budget, AI},
+    game::{engine, movement::all_available_moves, Board, Move, Rules},
+};
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    Wsp,
+    IsReady,
+    NewGame,
+    SetOption { name: String, value: String },
+    Position(Board),
+    Moves(Vec<Move>),
+    Go(Budget),
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reply {
+    Id(String),
+    WspOk,
+    ReadyOk,
+    BestMove(Option<Move>),
+    Info(String),
+}
+
+fn parse_moves<'a>(tokens: impl Iterator<Item = &'a str>) -> Result<Vec<Move>, String> {
+    tokens
+        .map(|m| m.parse().map_err(|e| format!("{e}")))
+        .collect()
+}
+
+fn parse_go<'a>(mut tokens: impl Iterator<Item = &'a str>) -> Result<Budget, String> {
+    let mut limits = Budget::default();
+    while let Some(key) = tokens.next() {
+        let value = tokens
+            .next()
+            .and_then(|v| v.parse::<u64>().ok())
+            .ok_or(format!("missing value for '{key}'"))?;
+        match key {
+            "depth" => {
+                limits.depth = Some(value.try_into().map_err(|_| format!("depth {value} is too deep"))?)
+            }
+            "movetime" => limits.movetime = Some(Duration::from_millis(value)),
+            "nodes" => limits.nodes = Some(value),
+            _ => return Err(format!("unknown go limit '{key}'")),
+        }
+    }
+    Ok(limits)
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split_whitespace();
+        let command = tokens.next().unwrap_or_default();
+        match command {
+            "wsp" => Ok(Command::Wsp),
+            "isready" => Ok(Command::IsReady),
+            "newgame" => Ok(Command::NewGame),
+            "setoption" => {
+                let rest: Vec<_> = tokens.collect();
+                match rest[..] {
+                    ["name", name, "value", value] => Ok(Command::SetOption {
+                        name: name.to_owned(),
+                        value: value.to_owned(),
+                    }),
+                    _ => Err(format!("malformed setoption '{s}'")),
+                }
+            }
+            "position" => {
+                let rest: Vec<_> = tokens.collect();
+                match rest[..] {
+                    ["startpos"] => Ok(Command::Position(Board::default())),
+                    _ => rest
+                        .join(" ")
+                        .parse()
+                        .map(Command::Position)
+                        .map_err(|e| format!("{e}")),
+                }
+            }
+            "moves" => parse_moves(tokens).map(Command::Moves),
+            "go" => parse_go(tokens).map(Command::Go),
+            "quit" => Ok(Command::Quit),
+            _ => Err(format!("unknown command '{command}'")),
+        }
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Command::Wsp => write!(f, "wsp"),
+            Command::IsReady => write!(f, "isready"),
+            Command::NewGame => write!(f, "newgame"),
+            Command::SetOption { name, value } => write!(f, "setoption name {name} value {value}"),
+            Command::Position(board) => write!(f, "position {board}"),
+            Command::Moves(moves) => {
+                write!(f, "moves")?;
+                moves.iter().try_for_each(|m| write!(f, " {m}"))
+            }
+            Command::Go(limits) => {
+                write!(f, "go")?;
+                if let Some(depth) = limits.depth {
+                    write!(f, " depth {depth}")?;
+                }
+                if let Some(movetime) = limits.movetime {
+                    write!(f, " movetime {}", movetime.as_millis())?;
+                }
+                if let Some(nodes) = limits.nodes {
+                    write!(f, " nodes {nodes}")?;
+                }
+                Ok(())
+            }
+            Command::Quit => write!(f, "quit"),
+        }
+    }
+}
+
+impl FromStr for Reply {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(name) = s.strip_prefix("id name ") {
+            Ok(Reply::Id(name.to_owned()))
+        } else if let Some(text) = s.strip_prefix("info string ") {
+            Ok(Reply::Info(text.to_owned()))
+        } else if let Some(mv) = s.strip_prefix("bestmove ") {
+            match mv.trim() {
+                "none" => Ok(Reply::BestMove(None)),
+                mv => mv
+                    .parse()
+                    .map(|m| Reply::BestMove(Some(m)))
+                    .map_err(|e| format!("{e}")),
+            }
+        } else {
+            match s {
+                "wspok" => Ok(Reply::WspOk),
+                "readyok" => Ok(Reply::ReadyOk),
+                _ => Err(format!("unknown reply '{s}'")),
+            }
+        }
+    }
+}
+
+impl fmt::Display for Reply {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reply::Id(name) => write!(f, "id name {name}"),
+            Reply::WspOk => write!(f, "wspok"),
+            Reply::ReadyOk => write!(f, "readyok"),
+            Reply::BestMove(Some(mv)) => write!(f, "bestmove {mv}"),
+            Reply::BestMove(None) => write!(f, "bestmove none"),
+            Reply::Info(text) => write!(f, "info string {text}"),
+        }
+    }
+}
+
+/// Engine side of the protocol, serving moves from one of our AIs.
+pub struct EngineSession {
+    board: Board,
+    rules: Rules,
+    ai_type: AITypes,
+    ai: Box<dyn AI + Send>,
+}
+
+impl EngineSession {
+    pub fn new(ai_type: AITypes) -> Self {
+        Self {
+            board: Board::default(),
+            rules: Rules::default(),
+            ai: get_ai(ai_type.clone(), &Rules::default()),
+            ai_type,
+        }
+    }
+
+    pub fn handle(&mut self, command: Command) -> Vec<Reply> {
+        match command {
+            Command::Wsp => vec![
+                Reply::Id(format!("wolf-and-sheep {}", self.ai_type)),
+                Reply::WspOk,
+            ],
+            Command::IsReady => vec![Reply::ReadyOk],
+            Command::NewGame => {
+                self.board = Board::default();
+                self.ai = get_ai(self.ai_type.clone(), &self.rules);
+                vec![]
+            }
+            Command::SetOption { name, value } if name.eq_ignore_ascii_case("ai") => {
+                match value.parse::<AITypes>() {
+                    Ok(ai_type) => {
+                        self.ai = get_ai(ai_type.clone(), &self.rules);
+                        self.ai_type = ai_type;
+                        vec![]
+                    }
+                    Err(e) => vec![Reply::Info(e)],
+                }
+            }
+            Command::SetOption { name, value } if name.eq_ignore_ascii_case("rules") => {
+                match value.parse() {
+                    Ok(rules) => {
+                        self.ai = get_ai(self.ai_type.clone(), &rules);
+                        self.rules = rules;
+                        vec![]
+                    }
+                    Err(e) => vec![Reply::Info(e)],
+                }
+            }
+            Command::SetOption { name, .. } => vec![Reply::Info(format!("unknown option '{name}'"))],
+            Command::Position(board) => match engine::game_outcome(&board, &self.rules) {
+                // the previous position stays, so a bad one can't be searched
+                Some(outcome) => vec![Reply::Info(format!("position {board} is already over: {outcome}"))],
+                None => {
+                    self.board = board;
+                    vec![]
+                }
+            },
+            Command::Moves(moves) => self.apply_moves(&moves),
+            Command::Go(_) if !self.ai_type.can_play(&self.board.currently_moving) => vec![
+                Reply::Info(format!("{} can't play {:?}", self.ai_type, self.board.currently_moving).to_lowercase()),
+                Reply::BestMove(None),
+            ],
+            Command::Go(budget) => {
+                let mv = self.ai.next_move_within(&self.board, &budget, &AtomicBool::new(false));
+                vec![Reply::BestMove(mv)]
+            }
+            Command::Quit => vec![],
+        }
+    }
+
+    fn apply_moves(&mut self, moves: &[Move]) -> Vec<Reply> {
+        for mv in moves {
+            if !all_available_moves(&self.board).contains(mv) {
+                return vec![Reply::Info(format!("illegal move {mv} in {}", self.board))];
+            }
+            let _ = engine::handle_move(&mut self.board, mv, &self.rules);
+        }
+        vec![]
+    }
+}
+
+pub fn serve(ai_type: AITypes, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    let mut session = EngineSession::new(ai_type);
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let replies = match line.parse() {
+            Ok(Command::Quit) => break,
+            Ok(command) => session.handle(command),
+            Err(e) => vec![Reply::Info(e)],
+        };
+        for reply in replies {
+            writeln!(output, "{reply}")?;
+        }
+        output.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(input: &str) -> Vec<String> {
+        let mut output = Vec::new();
+        serve(AITypes::Smart, input.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(str::to_owned)
+            .collect()
+    }
+
+    #[test]
+    fn commands_should_round_trip_through_text() {
+        let lines = [
+            "wsp",
+            "isready",
+            "newgame",
+            "setoption name ai value random",
+            "position d8 a1,c1,e1,g1 w",
+            "moves d8c7 a1b2",
+            "go depth 3 movetime 250 nodes 5000",
+            "quit",
+        ];
+        for line in lines {
+            let command: Command = line.parse().unwrap();
+            assert_eq!(command.to_string(), line);
+        }
+        assert!("position startpos".parse::<Command>().is_ok());
+        assert!("go mate 5".parse::<Command>().is_err());
+        assert!("go depth 300".parse::<Command>().is_err());
+    }
+
+    #[test]
+    fn serve_should_answer_handshake_and_go() {
+        let replies = run("wsp\nisready\nposition startpos\nmoves d8c7 a1b2\ngo movetime 100\nquit\ngo\n");
+
+        assert_eq!(replies[1], "wspok");
+        assert_eq!(replies[2], "readyok");
+        assert_eq!(replies.len(), 4, "nothing should be served after quit");
+        let best: Reply = replies[3].parse().unwrap();
+        let Reply::BestMove(Some(mv)) = best else {
+            panic!("expected a move, got {best:?}");
+        };
+        assert_eq!(mv.from.to_string(), "c7");
+    }
+
+    #[test]
+    fn serve_should_report_illegal_moves() {
+        let replies = run("position startpos\nmoves a1b2\n");
+
+        assert_eq!(replies.len(), 1);
+        assert!(replies[0].starts_with("info string illegal move a1b2"));
+    }
+
+    #[test]
+    fn serve_should_keep_the_last_position_instead_of_a_bad_one() {
+        let replies = run("position d8 d8,c1,e1,g1 w\nposition d2 a5,c5,e5,g5 w\ngo depth 1\n");
+
+        assert_eq!(replies.len(), 3);
+        assert!(replies[0].starts_with("info string invalid notation"));
+        assert_eq!(replies[1], "info string position d2 a5,c5,e5,g5 w is already over: Wolf won by wolf broke through");
+        assert!(replies[2].starts_with("bestmove d8"), "the start position should still be set");
+    }
+
+    #[test]
+    fn serve_should_not_move_for_a_side_the_ai_cannot_play() {
+        let replies = run("position d8 a1,c1,e1,g1 s\ngo depth 1\n");
+
+        assert_eq!(replies, ["info string smart can't play sheep", "bestmove none"]);
+    }
+
+    #[test]
+    fn serve_should_judge_positions_by_the_rules_set() {
+        let stuck_sheep = "position b8 f8,g7,h6,h8 s";
+        let replies = run(&format!(
+            "{stuck_sheep}\nsetoption name rules value loss,pass\n{stuck_sheep}\nsetoption name rules value pass\n"
+        ));
+
+        assert_eq!(replies.len(), 2, "the stuck sheep should pass under the new rules");
+        assert!(replies[0].ends_with("Wolf won by sheep immobilized"));
+        assert!(replies[1].starts_with("info string rules 'pass'"));
+    }
+}
@@ -0,0 +1,143 @@
+//! Who plays each side of a game and how the computer's turns are taken.
+//! Kept apart from [`crate::game`] so the rules don't depend on the AIs.
+
+mod pending;
+
+pub use pending::{PendingMoves, Thinking, Thought};
+
+use crate::{
+    ai::ComputerPlayer,
+    game::{GameOutcome, Species},
+};
+
+#[derive(Debug)]
+pub struct Controls {
+    pub wolf_controlled_by: Control,
+    pub sheep_controlled_by: Control,
+}
+
+#[derive(Debug)]
+pub enum Control {
+    Player,
+    Computer(ComputerPlayer),
+}
+
+impl Controls {
+    pub fn of(&self, species: &Species) -> &Control {
+        match species {
+            Species::Wolf => &self.wolf_controlled_by,
+            Species::Sheep => &self.sheep_controlled_by,
+        }
+    }
+
+    pub fn of_mut(&mut self, species: &Species) -> &mut Control {
+        match species {
+            Species::Wolf => &mut self.wolf_controlled_by,
+            Species::Sheep => &mut self.sheep_controlled_by,
+        }
+    }
+
+    /// Side the player plays, the one to move if they play both.
+    pub fn player_side(&self, to_move: &Species) -> Option<Species> {
+        [to_move.clone(), to_move.opposite()]
+            .into_iter()
+            .find(|species| matches!(self.of(species), Control::Player))
+    }
+
+    /// Why a computer player last failed to move, if one did.
+    pub fn take_error(&mut self) -> Option<String> {
+        [Species::Wolf, Species::Sheep]
+            .iter()
+            .find_map(|species| match self.of_mut(species) {
+                Control::Computer(player) => player.ai.take_error(),
+                Control::Player => None,
+            })
+    }
+}
+
+impl Default for Controls {
+    fn default() -> Self {
+        Self {
+            wolf_controlled_by: Control::Player,
+            sheep_controlled_by: Control::Player,
+        }
+    }
+}
+
+/// Hands `species` over to `control`, taking effect from its next move.
+pub fn handle_control_change(controls: &mut Controls, species: Species, control: Control) {
+    *controls.of_mut(&species) = control;
+}
+
+/// Tells every computer player how the game went from its own side.
+pub fn handle_game_end(controls: &mut Controls, outcome: &GameOutcome) {
+    for species in [Species::Wolf, Species::Sheep] {
+        if let Control::Computer(player) = controls.of_mut(&species) {
+            player.ai.feedback(outcome, &species);
+        }
+    }
+}
+
+pub fn handle_swap_sides(controls: &mut Controls) {
+    std::mem::swap(
+        &mut controls.wolf_controlled_by,
+        &mut controls.sheep_controlled_by,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::{channel, Sender};
+
+    use super::*;
+    use crate::{
+        ai::{AITypes, AI},
+        game::{Board, Move},
+    };
+
+    /// Plays a fixed move and reports every feedback it gets.
+    #[derive(Debug)]
+    pub(super) struct Scripted {
+        mv: Option<Move>,
+        feedback: Sender<(GameOutcome, Species)>,
+    }
+
+    impl AI for Scripted {
+        fn next_move(&mut self, _board: &Board) -> Option<Move> {
+            self.mv.clone()
+        }
+
+        fn feedback(&mut self, outcome: &GameOutcome, played_as: &Species) {
+            self.feedback.send((outcome.clone(), played_as.clone())).unwrap();
+        }
+    }
+
+    /// Computer playing `mv`, or no move at all for `none`.
+    pub(super) fn scripted(mv: &str, feedback: Sender<(GameOutcome, Species)>) -> Control {
+        Control::Computer(ComputerPlayer {
+            kind: AITypes::Random,
+            ai: Box::new(Scripted {
+                mv: mv.parse().ok(),
+                feedback,
+            }),
+        })
+    }
+
+    #[test]
+    fn handle_game_end_should_give_each_side_its_own_feedback() {
+        let (sender, feedback) = channel();
+        let mut controls = Controls {
+            wolf_controlled_by: scripted("none", sender.clone()),
+            sheep_controlled_by: scripted("none", sender),
+        };
+        let resignation = GameOutcome::resignation(&Species::Sheep);
+
+        handle_game_end(&mut controls, &resignation);
+
+        let feedback: Vec<_> = feedback.try_iter().collect();
+        assert_eq!(
+            feedback,
+            [(resignation.clone(), Species::Wolf), (resignation, Species::Sheep)]
+        );
+    }
+}
@@ -0,0 +1,346 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use super::{Control, Controls};
+use crate::{
+    ai::{Budget, AI},
+    game::{engine, movement::all_available_moves, Board, GameOutcome, GameRecord, Move, Species},
+};
+
+/// Stands in for an AI while it is away working out a move.
+#[derive(Debug)]
+struct Away;
+
+impl AI for Away {
+    fn next_move(&mut self, _board: &Board) -> Option<Move> {
+        None
+    }
+
+    fn feedback(&mut self, _outcome: &GameOutcome, _played_as: &Species) {}
+}
+
+/// The AI of the side to move, taken from its control to think elsewhere.
+#[derive(Debug)]
+pub struct Thinking {
+    ai: Box<dyn AI + Send>,
+    board: Board,
+    budget: Budget,
+    stop: Arc<AtomicBool>,
+}
+
+impl Thinking {
+    /// Works out the move, which may take long, so it is best run on its own thread.
+    /// An AI that panics gives up the game rather than taking the caller down.
+    pub fn think(mut self) -> Thought {
+        let thought = panic::catch_unwind(AssertUnwindSafe(|| {
+            self.ai.next_move_within(&self.board, &self.budget, &self.stop)
+        }));
+        let (mv, error) = match thought {
+            Ok(mv) => (mv, None),
+            Err(_) => (None, Some("AI crashed while thinking".to_owned())),
+        };
+        Thought {
+            ai: self.ai,
+            board: self.board,
+            mv,
+            error,
+            stop: self.stop,
+        }
+    }
+}
+
+/// A finished [`Thinking`], to be handed to [`PendingMoves::finish`].
+#[derive(Debug)]
+pub struct Thought {
+    ai: Box<dyn AI + Send>,
+    /// Position the AI was given.
+    board: Board,
+    mv: Option<Move>,
+    /// Why the AI failed to come up with a move, when it crashed.
+    error: Option<String>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Thought {
+    pub fn take_error(&mut self) -> Option<String> {
+        self.error.take()
+    }
+}
+
+#[derive(Debug)]
+struct Ticket {
+    stop: Arc<AtomicBool>,
+    /// Side the AI goes back to, `None` once that side got another control.
+    returns_to: Option<Species>,
+    /// Game the AI missed the end of while away, with the side it played.
+    missed: Option<(GameOutcome, Species)>,
+}
+
+/// Computer moves being worked out, so the caller never waits for an AI.
+#[derive(Debug, Default)]
+pub struct PendingMoves {
+    tickets: Vec<Ticket>,
+}
+
+impl PendingMoves {
+    fn is_away(&self, species: &Species) -> bool {
+        self.tickets
+            .iter()
+            .any(|t| t.returns_to.as_ref() == Some(species))
+    }
+
+    /// Takes the AI of the side to move, unless it's a player or already thinking.
+    pub fn start(
+        &mut self,
+        board: &Board,
+        controls: &mut Controls,
+        budget: &Budget,
+    ) -> Option<Thinking> {
+        let species = board.currently_moving.clone();
+        if self.is_away(&species) {
+            return None;
+        }
+        let Control::Computer(player) = controls.of_mut(&species) else {
+            return None;
+        };
+        let ai = std::mem::replace(&mut player.ai, Box::new(Away));
+        let stop = Arc::new(AtomicBool::new(false));
+        self.tickets.push(Ticket {
+            stop: stop.clone(),
+            returns_to: Some(species),
+            missed: None,
+        });
+        Some(Thinking {
+            ai,
+            board: board.clone(),
+            budget: budget.clone(),
+            stop,
+        })
+    }
+
+    /// Abandons every move being worked out, e.g. for a new game.
+    pub fn stop_all(&mut self) {
+        for ticket in &self.tickets {
+            ticket.stop.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Abandons the move of `species`, whose AI is dropped once it is done.
+    pub fn control_changed(&mut self, species: &Species) {
+        for ticket in &mut self.tickets {
+            if ticket.returns_to.as_ref() == Some(species) {
+                ticket.stop.store(true, Ordering::Relaxed);
+                ticket.returns_to = None;
+            }
+        }
+    }
+
+    pub fn sides_swapped(&mut self) {
+        for ticket in &mut self.tickets {
+            ticket.returns_to = ticket.returns_to.as_ref().map(Species::opposite);
+        }
+    }
+
+    /// Abandons all moves, keeping `outcome` for the AIs that missed it.
+    pub fn game_ended(&mut self, outcome: &GameOutcome) {
+        self.stop_all();
+        for ticket in &mut self.tickets {
+            if let Some(species) = &ticket.returns_to {
+                ticket.missed = Some((outcome.clone(), species.clone()));
+            }
+        }
+    }
+
+    /// Returns the AI to its side, then plays its move if it is still wanted
+    /// and legal in the position the AI was given.
+    #[must_use]
+    pub fn finish(
+        &mut self,
+        thought: Thought,
+        board: &mut Board,
+        record: &mut GameRecord,
+        controls: &mut Controls,
+    ) -> Option<GameOutcome> {
+        let index = self
+            .tickets
+            .iter()
+            .position(|t| Arc::ptr_eq(&t.stop, &thought.stop))?;
+        let ticket = self.tickets.remove(index);
+        let Thought { mut ai, board: given, mv, stop, .. } = thought;
+        if let Some((outcome, played_as)) = &ticket.missed {
+            ai.feedback(outcome, played_as);
+        }
+        if let Some(species) = &ticket.returns_to {
+            if let Control::Computer(player) = controls.of_mut(species) {
+                player.ai = ai;
+            }
+        }
+        // a move for a position the game has since left is of no use
+        if stop.load(Ordering::Relaxed) || board.to_string() != given.to_string() {
+            return None;
+        }
+        // positions without moves are resolved after every move,
+        // so an AI that doesn't move here is giving up
+        match mv {
+            Some(mv) if all_available_moves(&given).contains(&mv) => {
+                engine::handle_recorded_move(board, record, &mv)
+            }
+            Some(_) => None,
+            None => Some(GameOutcome::resignation(&board.currently_moving)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::{channel, Receiver};
+
+    use super::*;
+    use crate::session::{handle_control_change, tests::scripted};
+
+    /// Game where the computer plays the wolf by a script and the player the sheep.
+    struct Fixture {
+        controls: Controls,
+        board: Board,
+        record: GameRecord,
+        pending: PendingMoves,
+        feedback: Receiver<(GameOutcome, Species)>,
+    }
+
+    impl Fixture {
+        /// Wolf AI playing `mv`, already away thinking about its first move.
+        fn wolf_thinking(mv: &str) -> (Self, Thinking) {
+            let (sender, feedback) = channel();
+            let mut fixture = Self {
+                controls: Controls {
+                    wolf_controlled_by: scripted(mv, sender),
+                    sheep_controlled_by: Control::Player,
+                },
+                board: Board::default(),
+                record: GameRecord::default(),
+                pending: PendingMoves::default(),
+                feedback,
+            };
+            let thinking = fixture.pending.start(&fixture.board, &mut fixture.controls, &Budget::default()).unwrap();
+            (fixture, thinking)
+        }
+
+        fn finish(&mut self, thought: Thought) -> Option<GameOutcome> {
+            self.pending.finish(thought, &mut self.board, &mut self.record, &mut self.controls)
+        }
+
+        fn wolf_ai_is_back(&self) -> bool {
+            match self.controls.of(&Species::Wolf) {
+                Control::Computer(player) => format!("{:?}", player.ai).starts_with("Scripted"),
+                Control::Player => false,
+            }
+        }
+    }
+
+    #[test]
+    fn finish_should_play_the_move_and_return_the_ai() {
+        let (mut game, thinking) = Fixture::wolf_thinking("d8c7");
+
+        assert!(game.pending.start(&game.board, &mut game.controls, &Budget::default()).is_none());
+        assert!(!game.wolf_ai_is_back());
+        let outcome = game.finish(thinking.think());
+
+        assert_eq!(outcome, None);
+        assert_eq!(game.record.moves, ["d8c7".parse::<Move>().unwrap()]);
+        assert!(game.wolf_ai_is_back());
+    }
+
+    #[test]
+    fn finish_should_drop_the_move_of_a_stopped_ai() {
+        let (mut game, thinking) = Fixture::wolf_thinking("d8c7");
+
+        game.pending.stop_all();
+        let _ = game.finish(thinking.think());
+
+        assert!(game.record.moves.is_empty());
+        assert!(game.wolf_ai_is_back());
+    }
+
+    #[test]
+    fn finish_should_drop_a_move_for_a_position_left_meanwhile() {
+        let (mut game, thinking) = Fixture::wolf_thinking("d8c7");
+
+        let _ = engine::handle_recorded_move(&mut game.board, &mut game.record, &"d8e7".parse().unwrap());
+        let outcome = game.finish(thinking.think());
+
+        assert_eq!(outcome, None);
+        assert_eq!(game.record.moves, ["d8e7".parse::<Move>().unwrap()]);
+        assert!(game.wolf_ai_is_back());
+    }
+
+    #[test]
+    fn finish_should_drop_an_illegal_move() {
+        let (mut game, thinking) = Fixture::wolf_thinking("d8e5");
+
+        let outcome = game.finish(thinking.think());
+
+        assert_eq!(outcome, None);
+        assert!(game.record.moves.is_empty());
+    }
+
+    #[test]
+    fn finish_should_resign_for_ai_without_move() {
+        let (mut game, thinking) = Fixture::wolf_thinking("none");
+
+        let outcome = game.finish(thinking.think());
+
+        assert_eq!(outcome, Some(GameOutcome::resignation(&Species::Wolf)));
+    }
+
+    #[test]
+    fn think_should_give_up_for_an_ai_that_panics() {
+        #[derive(Debug)]
+        struct Panicking;
+        impl AI for Panicking {
+            fn next_move(&mut self, _board: &Board) -> Option<Move> {
+                panic!("lost in thought")
+            }
+            fn feedback(&mut self, _outcome: &GameOutcome, _played_as: &Species) {}
+        }
+        let thinking = Thinking {
+            ai: Box::new(Panicking),
+            board: Board::default(),
+            budget: Budget::default(),
+            stop: Arc::default(),
+        };
+
+        let mut thought = thinking.think();
+
+        assert_eq!(thought.mv, None);
+        assert!(thought.take_error().is_some());
+    }
+
+    #[test]
+    fn finish_should_not_return_the_ai_after_control_change() {
+        let (mut game, thinking) = Fixture::wolf_thinking("d8c7");
+
+        game.pending.control_changed(&Species::Wolf);
+        handle_control_change(&mut game.controls, Species::Wolf, Control::Player);
+        let _ = game.finish(thinking.think());
+
+        assert!(game.record.moves.is_empty());
+        assert!(matches!(game.controls.of(&Species::Wolf), Control::Player));
+    }
+
+    #[test]
+    fn finish_should_pass_on_the_outcome_missed_while_away() {
+        let (mut game, thinking) = Fixture::wolf_thinking("d8c7");
+        let resignation = GameOutcome::resignation(&Species::Wolf);
+
+        game.pending.game_ended(&resignation);
+        let _ = game.finish(thinking.think());
+
+        let feedback: Vec<_> = game.feedback.try_iter().collect();
+        assert_eq!(feedback, [(resignation, Species::Wolf)]);
+    }
+}
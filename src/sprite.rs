@@ -0,0 +1,492 @@
+use std::{fmt, str::FromStr, sync::Arc};
+
+use iced::{
+    canvas::{path::Builder, Frame, Path},
+    Color, Point, Rectangle, Size, Vector,
+};
+
+use crate::theme::parse_color;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpriteError(pub String);
+
+impl fmt::Display for SpriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for SpriteError {}
+
+fn error<T>(message: impl Into<String>) -> Result<T, SpriteError> {
+    Err(SpriteError(message.into()))
+}
+
+/// Vector image drawn on the board canvas, which can't show bitmaps.
+///
+/// Read from the part of SVG it understands: `path`, `rect`, `circle` and
+/// `polygon` elements with a solid `fill`, which `g` elements pass on to
+/// their children. Paths take the `M L H V C Q Z` commands, lowercase for
+/// relative ones. Anything else that would be drawn, like `image` bitmaps,
+/// `ellipse`, `text` or a `transform`, is refused rather than left out.
+/// Elements drawing nothing, like `title` or `defs`, are skipped.
+#[derive(Debug)]
+pub struct Sprite {
+    view_box: Rectangle,
+    shapes: Vec<(Path, Color)>,
+}
+
+/// Sprites drawn instead of the plain pins and squares, where there are any.
+#[derive(Debug, Clone, Default)]
+pub struct Sprites {
+    pub wolf: Option<Arc<Sprite>>,
+    pub sheep: Option<Arc<Sprite>>,
+    pub board: Option<Arc<Sprite>>,
+}
+
+impl Sprite {
+    pub fn load(file: &str) -> Result<Self, SpriteError> {
+        std::fs::read_to_string(file)
+            .map_err(|e| SpriteError(e.to_string()))?
+            .parse()
+    }
+
+    /// Draws the sprite as large as it fits in a `size` wide square around `center`.
+    pub fn draw(&self, frame: &mut Frame, center: Point, size: f32) {
+        let view_box = self.view_box;
+        let scale = size / view_box.width.max(view_box.height);
+        frame.with_save(|frame| {
+            frame.translate(Vector::new(
+                center.x - view_box.width * scale / 2.,
+                center.y - view_box.height * scale / 2.,
+            ));
+            frame.scale(scale);
+            frame.translate(Vector::new(-view_box.x, -view_box.y));
+            for (path, color) in &self.shapes {
+                frame.fill(path, *color);
+            }
+        });
+    }
+}
+
+/// `key="value"` pairs of an element.
+type Attributes<'a> = Vec<(&'a str, &'a str)>;
+
+/// Name and attributes of an element's opening tag.
+fn element(tag: &str) -> Result<(&str, Attributes<'_>), SpriteError> {
+    let tag = tag.trim_end_matches('/').trim();
+    let (name, mut rest) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+    let mut attributes = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Ok((name, attributes));
+        }
+        let Some((key, value)) = rest.split_once('=') else {
+            return error(format!("attribute without value in <{name}>"));
+        };
+        let value = value.trim_start();
+        let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            return error(format!("unquoted attribute {key} in <{name}>"));
+        };
+        let Some((value, after)) = value[1..].split_once(quote) else {
+            return error(format!("unclosed attribute {key} in <{name}>"));
+        };
+        attributes.push((key.trim(), value));
+        rest = after;
+    }
+}
+
+fn attribute<'a>(attributes: &[(&str, &'a str)], key: &str) -> Option<&'a str> {
+    attributes.iter().find(|(k, _)| *k == key).map(|(_, value)| *value)
+}
+
+fn number(attributes: &[(&str, &str)], key: &str) -> Result<f32, SpriteError> {
+    let value = attribute(attributes, key).unwrap_or("0");
+    // lengths may come with a unit, which is taken to be pixels
+    value
+        .trim_end_matches(|c: char| c.is_ascii_alphabetic())
+        .parse()
+        .or_else(|_| error(format!("invalid {key} '{value}'")))
+}
+
+/// `None` for shapes that aren't filled.
+fn svg_color(value: &str) -> Result<Option<Color>, SpriteError> {
+    let color = match value.trim() {
+        "none" | "transparent" => return Ok(None),
+        "black" => Some(Color::BLACK),
+        "white" => Some(Color::WHITE),
+        short if short.len() == 4 && short.starts_with('#') => {
+            let long: String = short[1..].chars().flat_map(|c| [c, c]).collect();
+            parse_color(&format!("#{long}"))
+        }
+        hex => parse_color(hex),
+    };
+    match color {
+        Some(color) => Ok(Some(color)),
+        None => error(format!("unknown color '{value}'")),
+    }
+}
+
+/// Fill of an element, from its `fill` attribute or its `style`,
+/// falling back to the one it inherits.
+fn fill(attributes: &[(&str, &str)], inherited: Option<Color>) -> Result<Option<Color>, SpriteError> {
+    let styled = attribute(attributes, "style").and_then(|style| {
+        style.split(';').find_map(|rule| {
+            let (key, value) = rule.split_once(':')?;
+            (key.trim() == "fill").then_some(value)
+        })
+    });
+    let mut color = match styled.or(attribute(attributes, "fill")) {
+        Some(value) => svg_color(value)?,
+        None => inherited,
+    };
+    if let (Some(color), Some(opacity)) = (&mut color, attribute(attributes, "fill-opacity")) {
+        color.a *= opacity
+            .parse::<f32>()
+            .or_else(|_| error(format!("invalid fill-opacity '{opacity}'")))?;
+    }
+    Ok(color)
+}
+
+/// Numbers of a path or point list, which may be split by spaces, commas,
+/// signs, or a second decimal point.
+fn numbers(mut text: &str) -> Result<Vec<f32>, SpriteError> {
+    let mut numbers = Vec::new();
+    loop {
+        text = text.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if text.is_empty() {
+            return Ok(numbers);
+        }
+        let bytes = text.as_bytes();
+        let mut end = usize::from(matches!(bytes[0], b'+' | b'-'));
+        let mut seen_point = false;
+        while let Some(&b) = bytes.get(end) {
+            match b {
+                b'0'..=b'9' => {}
+                b'.' if !seen_point => seen_point = true,
+                b'e' | b'E' => {
+                    end += usize::from(matches!(bytes.get(end + 1), Some(b'+' | b'-')));
+                }
+                _ => break,
+            }
+            end += 1;
+        }
+        match text[..end].parse() {
+            Ok(number) => numbers.push(number),
+            Err(_) => return error(format!("invalid number in '{text}'")),
+        }
+        text = &text[end..];
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Move(Point),
+    Line(Point),
+    Quadratic(Point, Point),
+    Cubic(Point, Point, Point),
+    Close,
+}
+
+/// Splits path data into segments with absolute points.
+fn segments(data: &str) -> Result<Vec<Segment>, SpriteError> {
+    let mut segments = Vec::new();
+    let mut current = Point::ORIGIN;
+    let mut start = Point::ORIGIN;
+    let mut rest = data.trim();
+    while let Some(command) = rest.chars().next() {
+        let count = match command.to_ascii_uppercase() {
+            'M' | 'L' => 2,
+            'H' | 'V' => 1,
+            'Q' => 4,
+            'C' => 6,
+            'Z' => 0,
+            _ => return error(format!("unsupported path command '{command}'")),
+        };
+        let arguments_end = rest[1..]
+            .find(|c: char| c.is_ascii_alphabetic() && c != 'e' && c != 'E')
+            .map_or(rest.len(), |end| end + 1);
+        let arguments = numbers(&rest[1..arguments_end])?;
+        rest = rest[arguments_end..].trim_start();
+        let relative = command.is_ascii_lowercase();
+        let point = |x: f32, y: f32, current: Point| match relative {
+            true => Point::new(current.x + x, current.y + y),
+            false => Point::new(x, y),
+        };
+        if count == 0 {
+            segments.push(Segment::Close);
+            current = start;
+            continue;
+        }
+        if arguments.is_empty() || arguments.len() % count != 0 {
+            return error(format!("wrong number of arguments for '{command}'"));
+        }
+        for (index, a) in arguments.chunks(count).enumerate() {
+            let segment = match command.to_ascii_uppercase() {
+                // further pairs after a move are lines
+                'M' if index == 0 => {
+                    start = point(a[0], a[1], current);
+                    Segment::Move(start)
+                }
+                'M' | 'L' => Segment::Line(point(a[0], a[1], current)),
+                'H' => Segment::Line(Point::new(if relative { current.x + a[0] } else { a[0] }, current.y)),
+                'V' => Segment::Line(Point::new(current.x, if relative { current.y + a[0] } else { a[0] })),
+                'Q' => Segment::Quadratic(point(a[0], a[1], current), point(a[2], a[3], current)),
+                _ => Segment::Cubic(
+                    point(a[0], a[1], current),
+                    point(a[2], a[3], current),
+                    point(a[4], a[5], current),
+                ),
+            };
+            current = match &segment {
+                Segment::Move(to) | Segment::Line(to) | Segment::Quadratic(_, to) | Segment::Cubic(_, _, to) => *to,
+                Segment::Close => start,
+            };
+            segments.push(segment);
+        }
+    }
+    Ok(segments)
+}
+
+fn trace(builder: &mut Builder, segments: &[Segment]) {
+    for segment in segments {
+        match segment {
+            Segment::Move(to) => builder.move_to(*to),
+            Segment::Line(to) => builder.line_to(*to),
+            Segment::Quadratic(control, to) => builder.quadratic_curve_to(*control, *to),
+            Segment::Cubic(first, second, to) => builder.bezier_curve_to(*first, *second, *to),
+            Segment::Close => builder.close(),
+        }
+    }
+}
+
+/// Outline of a shape element, `None` for elements that aren't drawn.
+fn shape(name: &str, attributes: &[(&str, &str)]) -> Result<Option<Path>, SpriteError> {
+    let path = match name {
+        "path" => {
+            let segments = segments(attribute(attributes, "d").unwrap_or_default())?;
+            Path::new(|builder| trace(builder, &segments))
+        }
+        "rect" => Path::rectangle(
+            Point::new(number(attributes, "x")?, number(attributes, "y")?),
+            Size::new(number(attributes, "width")?, number(attributes, "height")?),
+        ),
+        "circle" => Path::circle(
+            Point::new(number(attributes, "cx")?, number(attributes, "cy")?),
+            number(attributes, "r")?,
+        ),
+        "polygon" => {
+            let points = numbers(attribute(attributes, "points").unwrap_or_default())?;
+            let corners: Vec<_> = points.chunks_exact(2).map(|p| Point::new(p[0], p[1])).collect();
+            Path::new(|builder| {
+                for (index, corner) in corners.iter().enumerate() {
+                    match index {
+                        0 => builder.move_to(*corner),
+                        _ => builder.line_to(*corner),
+                    }
+                }
+                builder.close();
+            })
+        }
+        "image" | "ellipse" | "line" | "polyline" | "text" | "use" => {
+            return error(format!("<{name}> isn't supported"))
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(path))
+}
+
+fn view_box(attributes: &[(&str, &str)]) -> Result<Rectangle, SpriteError> {
+    if let Some(value) = attribute(attributes, "viewBox") {
+        return match numbers(value)?[..] {
+            [x, y, width, height] if width > 0. && height > 0. => {
+                Ok(Rectangle::new(Point::new(x, y), Size::new(width, height)))
+            }
+            _ => error(format!("invalid viewBox '{value}'")),
+        };
+    }
+    let size = Size::new(number(attributes, "width")?, number(attributes, "height")?);
+    match size.width > 0. && size.height > 0. {
+        true => Ok(Rectangle::new(Point::ORIGIN, size)),
+        false => error("the svg element needs a viewBox or a width and height"),
+    }
+}
+
+impl FromStr for Sprite {
+    type Err = SpriteError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut view_box_found = None;
+        let mut shapes = Vec::new();
+        // fills passed on by the `g` elements currently open
+        let mut fills = vec![Some(Color::BLACK)];
+        let mut rest = s;
+        while let Some(open) = rest.find('<') {
+            rest = &rest[open + 1..];
+            let end_marker = if rest.starts_with("!--") { "-->" } else { ">" };
+            let Some(close) = rest.find(end_marker) else {
+                return error("unclosed tag");
+            };
+            let tag = &rest[..close];
+            rest = &rest[close + end_marker.len()..];
+            if tag.starts_with(['!', '?']) {
+                continue;
+            }
+            if let Some(closing) = tag.strip_prefix('/') {
+                if closing.trim() == "g" && fills.len() > 1 {
+                    fills.pop();
+                }
+                continue;
+            }
+            let (name, attributes) = element(tag)?;
+            if attribute(&attributes, "transform").is_some() {
+                return error(format!("transform on <{name}> isn't supported"));
+            }
+            let inherited = *fills.last().unwrap();
+            match name {
+                "svg" => view_box_found = Some(view_box(&attributes)?),
+                "g" if !tag.ends_with('/') => fills.push(fill(&attributes, inherited)?),
+                _ => {
+                    if let (Some(path), Some(color)) = (shape(name, &attributes)?, fill(&attributes, inherited)?) {
+                        shapes.push((path, color));
+                    }
+                }
+            }
+        }
+        match view_box_found {
+            Some(view_box) => Ok(Sprite { view_box, shapes }),
+            None => error("no svg element"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: f32, y: f32) -> Point {
+        Point::new(x, y)
+    }
+
+    #[test]
+    fn numbers_should_split_on_spaces_commas_signs_and_second_points() {
+        assert_eq!(numbers(" 1,2 3-4.5.5e1 -1e-2").unwrap(), [1., 2., 3., -4.5, 5., -0.01]);
+        assert_eq!(numbers("").unwrap(), []);
+        assert!(numbers("1 x").is_err());
+    }
+
+    #[test]
+    fn segments_should_read_absolute_commands() {
+        let segments = segments("M10 20 L30,40 H5 V6 Q1 2 3 4 C1 2 3 4 5 6 Z").unwrap();
+
+        assert_eq!(
+            segments,
+            [
+                Segment::Move(p(10., 20.)),
+                Segment::Line(p(30., 40.)),
+                Segment::Line(p(5., 40.)),
+                Segment::Line(p(5., 6.)),
+                Segment::Quadratic(p(1., 2.), p(3., 4.)),
+                Segment::Cubic(p(1., 2.), p(3., 4.), p(5., 6.)),
+                Segment::Close,
+            ]
+        );
+    }
+
+    #[test]
+    fn segments_should_read_relative_commands_from_the_current_point() {
+        let segments = segments("m10 20 l5 5 h10 v-5 q1 1 2 2 z m1 1").unwrap();
+
+        assert_eq!(
+            segments,
+            [
+                Segment::Move(p(10., 20.)),
+                Segment::Line(p(15., 25.)),
+                Segment::Line(p(25., 25.)),
+                Segment::Line(p(25., 20.)),
+                Segment::Quadratic(p(26., 21.), p(27., 22.)),
+                Segment::Close,
+                // closing goes back to the start of the subpath
+                Segment::Move(p(11., 21.)),
+            ]
+        );
+    }
+
+    #[test]
+    fn segments_should_take_pairs_after_a_move_as_lines() {
+        assert_eq!(
+            segments("M0 0 10 10 20 0").unwrap(),
+            [Segment::Move(p(0., 0.)), Segment::Line(p(10., 10.)), Segment::Line(p(20., 0.))]
+        );
+        assert_eq!(
+            segments("m1 1 2 2").unwrap(),
+            [Segment::Move(p(1., 1.)), Segment::Line(p(3., 3.))]
+        );
+    }
+
+    #[test]
+    fn segments_should_reject_what_they_cannot_draw() {
+        assert!(segments("M0 0 A 1 1 0 0 1 2 2").is_err());
+        assert!(segments("M1").is_err());
+        assert!(segments("M0 0 L").is_err());
+        assert!(segments("M0 0 é").is_err());
+    }
+
+    #[test]
+    fn view_box_should_fall_back_to_width_and_height() {
+        assert_eq!(
+            view_box(&[("viewBox", "-5 0 10 20")]).unwrap(),
+            Rectangle::new(p(-5., 0.), Size::new(10., 20.))
+        );
+        assert_eq!(
+            view_box(&[("width", "32px"), ("height", "16")]).unwrap(),
+            Rectangle::new(Point::ORIGIN, Size::new(32., 16.))
+        );
+        assert!(view_box(&[("viewBox", "0 0 0 5")]).is_err());
+        assert!(view_box(&[("viewBox", "0 0 5")]).is_err());
+        assert!(view_box(&[]).is_err());
+    }
+
+    #[test]
+    fn sprite_should_read_filled_shapes_inheriting_group_fills() {
+        let svg = r##"<?xml version="1.0"?>
+            <svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10">
+              <!-- a <g> in a comment is ignored -->
+              <title>Wolf</title>
+              <g fill="#f00">
+                <circle cx="5" cy="5" r="4"/>
+                <rect width="2" height="2" fill="none"/>
+              </g>
+              <polygon points="0,0 10,0 5,10" style="fill: white"/>
+            </svg>"##;
+
+        let sprite: Sprite = svg.parse().unwrap();
+
+        assert_eq!(sprite.view_box, Rectangle::new(Point::ORIGIN, Size::new(10., 10.)));
+        let colors: Vec<_> = sprite.shapes.iter().map(|(_, color)| *color).collect();
+        assert_eq!(colors, [Color::from_rgb8(255, 0, 0), Color::WHITE]);
+    }
+
+    #[test]
+    fn sprite_should_reject_broken_files() {
+        assert!("<g></g>".parse::<Sprite>().is_err());
+        assert!(r#"<svg viewBox="0 0 1 1"><rect"#.parse::<Sprite>().is_err());
+        assert!(r#"<svg viewBox="0 0 1 1"><rect fill="teal"/></svg>"#.parse::<Sprite>().is_err());
+        assert!(r#"<svg viewBox="0 0 1 1"><circle r="big"/></svg>"#.parse::<Sprite>().is_err());
+    }
+
+    #[test]
+    fn sprite_should_refuse_what_it_cannot_draw() {
+        let refused = [
+            r#"<image href="wolf.png" width="1" height="1"/>"#,
+            r#"<ellipse rx="1" ry="2"/>"#,
+            r#"<text>wolf</text>"#,
+            r#"<g transform="rotate(45)"><rect width="1" height="1"/></g>"#,
+        ];
+        for element in refused {
+            let svg = format!(r#"<svg viewBox="0 0 1 1">{element}</svg>"#);
+            assert!(svg.parse::<Sprite>().is_err(), "{element}");
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use std::{fmt, sync::Arc};
+
+use iced::Color;
+
+use crate::sprite::{Sprite, Sprites};
+
+/// File extra themes are read from at startup, in the working directory.
+pub const THEMES_FILE: &str = "wolf-and-sheep-themes.txt";
+
+/// Colors the board is drawn in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    pub light_square: Color,
+    pub dark_square: Color,
+    pub wolf: Color,
+    pub sheep: Color,
+    /// Ring around the selected pin.
+    pub selected: Color,
+    /// Markers on the squares the selected pin can go to.
+    pub destination: Color,
+    /// Squares of the last move.
+    pub last_move: Color,
+    /// Squares the wolf reaches next turn.
+    pub threat: Color,
+    /// Squares from which the sheep can no longer stop the wolf.
+    pub breakthrough: Color,
+    pub illegal_click: Color,
+    /// Coordinates around the board.
+    pub label: Color,
+}
+
+const CLASSIC: Palette = Palette {
+    light_square: Color::WHITE,
+    dark_square: Color::BLACK,
+    wolf: Color::from_rgb(0.8, 0.5, 0.2),
+    sheep: Color::from_rgb(1., 1., 0.),
+    selected: Color::from_rgb(1., 0., 0.),
+    destination: Color::from_rgba(0.2, 0.8, 0.2, 0.8),
+    last_move: Color::from_rgba(1., 0.9, 0.3, 0.45),
+    threat: Color::from_rgba(1., 0.55, 0., 0.4),
+    breakthrough: Color::from_rgba(0.9, 0.1, 0.1, 0.6),
+    illegal_click: Color::from_rgb(1., 0., 0.),
+    label: Color::from_rgb(0.3, 0.3, 0.3),
+};
+
+/// Pins only stand on dark squares, so they get the brightest colors.
+const HIGH_CONTRAST: Palette = Palette {
+    light_square: Color::WHITE,
+    dark_square: Color::BLACK,
+    wolf: Color::from_rgb(0., 1., 1.),
+    sheep: Color::from_rgb(1., 1., 0.),
+    selected: Color::from_rgb(1., 0., 1.),
+    destination: Color::from_rgb(0., 1., 0.),
+    last_move: Color::from_rgba(1., 1., 1., 0.4),
+    threat: Color::from_rgba(1., 0.5, 0., 0.75),
+    breakthrough: Color::from_rgba(1., 0., 0., 0.9),
+    illegal_click: Color::from_rgb(1., 0., 1.),
+    label: Color::BLACK,
+};
+
+/// Okabe-Ito colors, which stay apart for every common color blindness.
+const COLOR_BLIND: Palette = Palette {
+    light_square: Color::from_rgb(0.94, 0.94, 0.94),
+    dark_square: Color::from_rgb(0.3, 0.3, 0.3),
+    wolf: Color::from_rgb(0.9, 0.62, 0.),
+    sheep: Color::from_rgb(0.34, 0.71, 0.91),
+    selected: Color::from_rgb(0.8, 0.47, 0.65),
+    destination: Color::from_rgba(0., 0.62, 0.45, 0.9),
+    last_move: Color::from_rgba(0.94, 0.89, 0.26, 0.45),
+    threat: Color::from_rgba(0., 0.45, 0.7, 0.5),
+    breakthrough: Color::from_rgba(0.84, 0.37, 0., 0.75),
+    illegal_click: Color::from_rgb(0.8, 0.47, 0.65),
+    label: Color::from_rgb(0.2, 0.2, 0.2),
+};
+
+impl Default for Palette {
+    fn default() -> Self {
+        CLASSIC
+    }
+}
+
+impl Palette {
+    fn color_mut(&mut self, role: &str) -> Option<&mut Color> {
+        match role {
+            "light" => Some(&mut self.light_square),
+            "dark" => Some(&mut self.dark_square),
+            "wolf" => Some(&mut self.wolf),
+            "sheep" => Some(&mut self.sheep),
+            "selected" => Some(&mut self.selected),
+            "destination" => Some(&mut self.destination),
+            "last-move" => Some(&mut self.last_move),
+            "threat" => Some(&mut self.threat),
+            "breakthrough" => Some(&mut self.breakthrough),
+            "illegal" => Some(&mut self.illegal_click),
+            "label" => Some(&mut self.label),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub name: String,
+    pub palette: Palette,
+    pub sprites: Sprites,
+}
+
+impl Theme {
+    fn new(name: &str, palette: Palette) -> Self {
+        Self {
+            name: name.to_owned(),
+            palette,
+            sprites: Sprites::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThemeError {
+    pub line: usize,
+    pub text: String,
+    /// What went wrong beyond the line not making sense, like a missing image.
+    pub reason: Option<String>,
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: can't read '{}'", self.line, self.text)?;
+        match &self.reason {
+            Some(reason) => write!(f, " ({reason})"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+/// `#rrggbb`, or `#rrggbbaa` for see-through colors.
+pub fn parse_color(s: &str) -> Option<Color> {
+    let hex = s.strip_prefix('#').filter(|hex| matches!(hex.len(), 6 | 8))?;
+    let channel = |index: usize| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok();
+    let alpha = match hex.len() {
+        8 => channel(6)?,
+        _ => u8::MAX,
+    };
+    Some(Color::from_rgba8(channel(0)?, channel(2)?, channel(4)?, f32::from(alpha) / 255.))
+}
+
+/// Built-in themes plus those of a themes file, and the one in use.
+#[derive(Debug, Clone)]
+pub struct Themes {
+    pub all: Vec<Theme>,
+    current: usize,
+}
+
+impl Default for Themes {
+    fn default() -> Self {
+        Self {
+            all: vec![
+                Theme::new("Classic", CLASSIC),
+                Theme::new("High contrast", HIGH_CONTRAST),
+                Theme::new("Color blind", COLOR_BLIND),
+            ],
+            current: 0,
+        }
+    }
+}
+
+impl Themes {
+    pub fn current(&self) -> &Theme {
+        &self.all[self.current]
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.all.iter().map(|theme| theme.name.clone()).collect()
+    }
+
+    /// Switches to the theme called `name`, if there is one.
+    pub fn select(&mut self, name: &str) {
+        if let Some(index) = self.all.iter().position(|theme| theme.name == name) {
+            self.current = index;
+        }
+    }
+
+    /// Adds the themes of a themes file, replacing built-ins of the same name:
+    /// ```text
+    /// default Night
+    /// theme Night
+    /// light #404040
+    /// dark #101010
+    /// last-move #ffffff40
+    /// ```
+    /// Each theme starts from the classic colors, so it only lists what it changes.
+    /// Colors are named `light`, `dark`, `wolf`, `sheep`, `selected`, `destination`,
+    /// `last-move`, `threat`, `breakthrough`, `illegal` and `label`.
+    ///
+    /// `wolf-image`, `sheep-image` and `board-image` name SVG files drawn
+    /// instead of the plain pins and squares, using only the shapes and solid
+    /// fills a [`Sprite`] understands. A `board-image` isn't turned around
+    /// with the board, so a flipped board keeps the plain squares. An image
+    /// that can't be read leaves its theme with the plain drawing, and is
+    /// only reported once the rest of the file is loaded.
+    pub fn load(&mut self, text: &str) -> Result<(), ThemeError> {
+        let mut default = None;
+        let mut broken_image = None;
+        let mut editing: Option<usize> = None;
+        for (index, line) in text.lines().enumerate() {
+            let error = || ThemeError {
+                line: index + 1,
+                text: line.to_owned(),
+                reason: None,
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once(' ').ok_or_else(error)?;
+            let value = value.trim();
+            match key {
+                "default" => default = Some(value.to_owned()),
+                "theme" => {
+                    let theme = Theme::new(value, Palette::default());
+                    editing = Some(match self.all.iter().position(|t| t.name == value) {
+                        Some(existing) => {
+                            self.all[existing] = theme;
+                            existing
+                        }
+                        None => {
+                            self.all.push(theme);
+                            self.all.len() - 1
+                        }
+                    });
+                }
+                "wolf-image" | "sheep-image" | "board-image" => {
+                    let theme = &mut self.all[editing.ok_or_else(error)?];
+                    let sprite = match key {
+                        "wolf-image" => &mut theme.sprites.wolf,
+                        "sheep-image" => &mut theme.sprites.sheep,
+                        _ => &mut theme.sprites.board,
+                    };
+                    match Sprite::load(value) {
+                        Ok(loaded) => *sprite = Some(Arc::new(loaded)),
+                        Err(e) => {
+                            broken_image.get_or_insert(ThemeError {
+                                reason: Some(e.to_string()),
+                                ..error()
+                            });
+                        }
+                    }
+                }
+                role => {
+                    let theme = editing.ok_or_else(error)?;
+                    let color = self.all[theme].palette.color_mut(role).ok_or_else(error)?;
+                    *color = parse_color(value).ok_or_else(error)?;
+                }
+            }
+        }
+        if let Some(name) = default {
+            self.select(&name);
+        }
+        broken_image.map_or(Ok(()), Err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_color_should_read_an_optional_alpha() {
+        assert_eq!(parse_color("#ff0000"), Some(Color::from_rgb8(255, 0, 0)));
+        assert_eq!(parse_color("#ffffff40"), Some(Color::from_rgba8(255, 255, 255, 64. / 255.)));
+        assert_eq!(parse_color("ff0000"), None);
+        assert_eq!(parse_color("#ff00"), None);
+        assert_eq!(parse_color("#gg0000"), None);
+    }
+
+    #[test]
+    fn load_should_add_a_theme_and_switch_to_the_default() {
+        let mut themes = Themes::default();
+
+        let loaded = themes.load("# night play\ndefault Night\ntheme Night\nlight #404040\nlast-move #ffffff40\n");
+
+        assert_eq!(loaded, Ok(()));
+        assert_eq!(themes.current().name, "Night");
+        let palette = themes.current().palette;
+        assert_eq!(palette.light_square, Color::from_rgb8(0x40, 0x40, 0x40));
+        assert_eq!(palette.last_move, Color::from_rgba8(255, 255, 255, 64. / 255.));
+        assert_eq!(palette.dark_square, CLASSIC.dark_square, "unlisted colors stay classic");
+    }
+
+    #[test]
+    fn load_should_replace_a_built_in_theme_of_the_same_name() {
+        let mut themes = Themes::default();
+        let built_in = themes.all.len();
+
+        themes.load("theme High contrast\nwolf #123456\n").unwrap();
+
+        assert_eq!(themes.all.len(), built_in);
+        let replaced = &themes.all[1];
+        assert_eq!(replaced.name, "High contrast");
+        assert_eq!(replaced.palette.wolf, Color::from_rgb8(0x12, 0x34, 0x56));
+        assert_eq!(replaced.palette.light_square, CLASSIC.light_square);
+    }
+
+    #[test]
+    fn load_should_report_the_line_it_cannot_read() {
+        let errors = [
+            ("theme Night\nfur #000000\n", 2),
+            ("light #404040\ntheme Night\n", 1),
+            ("theme Night\n\nlight 404040\n", 3),
+            ("theme\n", 1),
+        ];
+        for (text, line) in errors {
+            let error = Themes::default().load(text).unwrap_err();
+            assert_eq!(error.line, line, "in {text:?}");
+        }
+    }
+
+    #[test]
+    fn load_should_keep_the_current_theme_for_an_unknown_default() {
+        let mut themes = Themes::default();
+
+        themes.load("default Nowhere\n").unwrap();
+
+        assert_eq!(themes.current().name, "Classic");
+    }
+}